@@ -1062,6 +1062,8 @@ impl U64BitOr of BitOr<u64> {
     }
 }
 
+/// A 256-bit unsigned integer, made of two 128-bit limbs. The derived `Serde` encodes it as the two
+/// felts `[low, high]`, the order used by the Starknet ABI and its clients.
 #[derive(Copy, Drop, PartialEq, Serde, storage_access::StorageAccess)]
 struct u256 {
     low: u128,
@@ -1303,6 +1305,8 @@ impl U256BitNot of BitNot<u256> {
     }
 }
 
+/// A 512-bit unsigned integer, made of four 128-bit limbs, from the least significant `limb0`. The
+/// derived `Serde` encodes it as the four felts `[limb0, limb1, limb2, limb3]`.
 #[derive(Copy, Drop, PartialEq, Serde)]
 struct u512 {
     limb0: u128,
@@ -2,6 +2,7 @@
 //! secp256k1 curve.
 
 use option::OptionTrait;
+use serde::Serde;
 use starknet::{
     EthAddress, secp256_trait::{Secp256Trait, Secp256PointTrait}, SyscallResult, SyscallResultTrait
 };
@@ -46,6 +47,23 @@ impl Secp256k1PointImpl of Secp256PointTrait<Secp256k1Point> {
     }
 }
 
+/// Serializes secp256k1 EC points as their x and y coordinates.
+/// Deserialization fails if the coordinates do not correspond to a point on the curve.
+impl Secp256k1PointSerde of Serde<Secp256k1Point> {
+    fn serialize(self: @Secp256k1Point, ref output: Array<felt252>) {
+        let (x, y) = secp256k1_get_xy_syscall(*self).unwrap_syscall();
+        x.serialize(ref output);
+        y.serialize(ref output);
+    }
+    /// Panics if the coordinates are not on the curve.
+    fn deserialize(ref serialized: Span<felt252>) -> Option<Secp256k1Point> {
+        let x = Serde::<u256>::deserialize(ref serialized)?;
+        let y = Serde::<u256>::deserialize(ref serialized)?;
+        let point = secp256k1_new_syscall(x, y).unwrap_syscall();
+        Option::Some(point.expect('Point must be on the curve'))
+    }
+}
+
 /// Creates a secp256k1 EC point from the given x and y coordinates.
 /// Returns None if the given coordinates do not correspond to a point on the curve.
 extern fn secp256k1_new_syscall(
@@ -1,7 +1,9 @@
 use starknet::{
     eth_address::U256IntoEthAddress, EthAddress, secp256k1::Secp256k1Impl, SyscallResultTrait
 };
+use array::ArrayTrait;
 use option::OptionTrait;
+use serde::Serde;
 use traits::{Into, TryInto};
 use starknet::secp256_trait::{
     recover_public_key, recover_public_key_u32, verify_eth_signature, Secp256PointTrait
@@ -56,6 +58,48 @@ fn test_secp256k1_recover_public_key_u32() {
     assert(expected_public_key_y == y, 'recover failed 4');
 }
 
+#[test]
+#[available_gas(100000000)]
+fn test_secp256k1_point_serde() {
+    let point = Secp256k1Impl::get_generator_point();
+    let mut serialized = ArrayTrait::new();
+    point.serialize(ref serialized);
+    assert(serialized.len() == 4, 'wrong serialized length');
+    let mut span = serialized.span();
+    let (x, y) = Serde::<(u256, u256)>::deserialize(ref span).unwrap();
+    let (expected_x, expected_y) = point.get_coordinates().unwrap_syscall();
+    assert(x == expected_x, 'wrong serialized x');
+    assert(y == expected_y, 'wrong serialized y');
+
+    let mut span = serialized.span();
+    let (x, y) = Serde::<Secp256k1Point>::deserialize(ref span)
+        .unwrap()
+        .get_coordinates()
+        .unwrap_syscall();
+    assert(x == expected_x, 'wrong deserialized x');
+    assert(y == expected_y, 'wrong deserialized y');
+}
+
+#[test]
+#[available_gas(100000000)]
+#[should_panic(expected: ('Point must be on the curve', ))]
+fn test_secp256k1_point_serde_not_on_curve() {
+    let mut serialized = ArrayTrait::new();
+    1_u256.serialize(ref serialized);
+    1_u256.serialize(ref serialized);
+    let mut span = serialized.span();
+    Serde::<Secp256k1Point>::deserialize(ref span);
+}
+
+#[test]
+#[available_gas(100000000)]
+fn test_secp256k1_point_serde_missing_coordinate() {
+    let mut serialized = ArrayTrait::new();
+    1_u256.serialize(ref serialized);
+    let mut span = serialized.span();
+    assert(Serde::<Secp256k1Point>::deserialize(ref span).is_none(), 'point should be missing');
+}
+
 /// Returns a golden valid message hash and its signature, for testing.
 fn get_message_and_signature(y_parity: bool) -> (u256, u256, u256, u256, u256, EthAddress) {
     let msg_hash = 0xe888fbb4cf9ae6254f19ba12e6d9af54788f195a6f509ca3e934f78d7a71dd85;
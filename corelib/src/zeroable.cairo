@@ -1,3 +1,7 @@
+use option::OptionTrait;
+use serde::Serde;
+use traits::{Into, TryInto};
+
 // === Zeroable ===
 
 trait Zeroable<T> {
@@ -40,6 +44,24 @@ impl NonZeroIntoImpl<T> of Into<NonZero<T>, T> {
     }
 }
 
+impl NonZeroSerde<
+    T,
+    impl TSerde: Serde<T>,
+    impl TCopy: Copy<T>,
+    impl TDrop: Drop<T>,
+    impl TTryIntoNonZero: TryInto<T, NonZero<T>>
+> of Serde<NonZero<T>> {
+    fn serialize(self: @NonZero<T>, ref output: Array<felt252>) {
+        let value: T = unwrap_non_zero(*self);
+        value.serialize(ref output)
+    }
+    /// Panics if the deserialized value is zero.
+    fn deserialize(ref serialized: Span<felt252>) -> Option<NonZero<T>> {
+        let value = TSerde::deserialize(ref serialized)?;
+        Option::Some(value.try_into().expect('Value must be non-zero'))
+    }
+}
+
 impl IsZeroResultIntoBool<T, impl TDrop: Drop<T>> of Into<IsZeroResult<T>, bool> {
     fn into(self: IsZeroResult<T>) -> bool {
         match self {
@@ -46,3 +46,9 @@ fn fib(a: Box::<felt252>, b: Box::<felt252>, n: Box::<felt252>) -> Box::<felt252
         },
     }
 }
+
+/// Doc comment of a trait.
+trait   T{
+/// Doc comment of a trait function.
+   fn foo( x:felt252 );
+}
@@ -941,6 +941,14 @@ fn module_semantic_diagnostics(
             SemanticDiagnosticKind::PluginDiagnostic(plugin_diag),
         ));
     }
+    for plugin in db.semantic_plugins() {
+        for (module_file_id, plugin_diag) in plugin.module_semantic_diagnostics(db, module_id) {
+            diagnostics.add(SemanticDiagnostic::new(
+                StableLocation::new(module_file_id, plugin_diag.stable_ptr),
+                SemanticDiagnosticKind::PluginDiagnostic(plugin_diag),
+            ));
+        }
+    }
 
     diagnostics.extend(db.priv_module_semantic_data(module_id)?.diagnostics.clone());
 
@@ -2,12 +2,24 @@ use std::any::Any;
 use std::ops::Deref;
 use std::sync::Arc;
 
-use cairo_lang_defs::plugin::{GeneratedFileAuxData, MacroPlugin};
+use cairo_lang_defs::ids::{ModuleFileId, ModuleId};
+use cairo_lang_defs::plugin::{GeneratedFileAuxData, MacroPlugin, PluginDiagnostic};
 use cairo_lang_filesystem::span::TextSpan;
 
 use crate::db::SemanticGroup;
 
-pub trait SemanticPlugin: std::fmt::Debug + Sync + Send + AsDynMacroPlugin {}
+pub trait SemanticPlugin: std::fmt::Debug + Sync + Send + AsDynMacroPlugin {
+    /// Returns additional diagnostics of the module, which may consult its semantic model, unlike
+    /// the diagnostics of the code generation. Reported along with the other semantic diagnostics
+    /// of the module.
+    fn module_semantic_diagnostics(
+        &self,
+        _db: &dyn SemanticGroup,
+        _module_id: ModuleId,
+    ) -> Vec<(ModuleFileId, PluginDiagnostic)> {
+        vec![]
+    }
+}
 
 pub trait AsDynMacroPlugin {
     fn as_dyn_macro_plugin<'a>(self: Arc<Self>) -> Arc<dyn MacroPlugin + 'a>
@@ -2,6 +2,7 @@ use array::ArrayTrait;
 use array::SpanTrait;
 use box::BoxTrait;
 use option::OptionTrait;
+use serde::Serde;
 use traits::{TryInto, Into};
 use zeroable::Zeroable;
 use clone::Clone;
@@ -16,12 +17,21 @@ use super::utils::single_deserialize;
 #[starknet::interface]
 trait ITestContract {}
 
+#[derive(Copy, Drop, Serde)]
+struct Amount {
+    value: felt252,
+}
+
 #[starknet::contract]
+#[record_args]
+#[is_view]
+#[entry_points]
 mod TestContract {
     use array::ArrayTrait;
     use option::OptionTrait;
     use traits::Into;
-    use starknet::StorageAddress;
+    use starknet::{ContractAddress, StorageAddress};
+    use super::Amount;
 
     #[storage]
     struct Storage {
@@ -35,6 +45,16 @@ mod TestContract {
         a + 2
     }
 
+    #[external(v0)]
+    fn get_doubled_amount(self: @ContractState, amount: Amount) -> Amount {
+        Amount { value: amount.value * 2 }
+    }
+
+    #[external(v0)]
+    fn get_next_index(self: @ContractState, index: usize) -> usize {
+        index + 1
+    }
+
     #[external(v0)]
     fn spend_all_gas(self: @ContractState) {
         spend_all_gas(self);
@@ -88,6 +108,80 @@ mod TestContract {
     ) -> StorageAddress {
         storage_address
     }
+
+    #[external(v0)]
+    fn double_in_place(self: @ContractState, ref value: u128, ref amount: Amount) {
+        value = 2 * value;
+        amount = Amount { value: 2 * amount.value };
+    }
+
+    #[external(v0)]
+    #[view_external]
+    fn sync_value(self: @ContractState) -> felt252 {
+        self.value.read()
+    }
+
+    #[external(v0)]
+    fn append_in_place(self: @ContractState, ref values: Array<felt252>, value: felt252) {
+        values.append(value);
+    }
+
+    #[external(v0)]
+    fn divide(self: @ContractState, a: felt252, b: NonZero<felt252>) -> felt252 {
+        felt252_div(a, b)
+    }
+
+    #[external(v0)]
+    #[returns_optional]
+    fn get_value_if_set(self: @ContractState) -> felt252 {
+        self.value.read()
+    }
+
+    #[external(v0)]
+    fn get_holders(self: @ContractState) -> Array<ContractAddress> {
+        let mut holders = ArrayTrait::new();
+        holders.append(starknet::contract_address_const::<0x10>());
+        holders.append(starknet::contract_address_const::<0x20>());
+        holders
+    }
+}
+
+#[test]
+fn test_is_view() {
+    // The selector of `get_plus_2`.
+    assert(
+        TestContract::__is_view(0xd81d9282d6205833196c90d88e91504da20610c82b92c864b334d839ff3a75),
+        'get_plus_2 is a view'
+    );
+    // The selector of `set_value`.
+    assert(
+        !TestContract::__is_view(0x3d7905601c217734671143d457f0db37f7f8883112abd34b92c4abfeafde0c3),
+        'set_value is not a view'
+    );
+    // The selector of `sync_value`, which takes `self` as a snapshot but is marked as mutating.
+    assert(
+        !TestContract::__is_view(0x1bae308f8d8135e652f12c8913a5219a674de451ea999768f259c2743e9a56e),
+        'sync_value is not a view'
+    );
+}
+
+#[test]
+#[should_panic]
+fn test_is_view_unknown_selector() {
+    TestContract::__is_view(0);
+}
+
+#[test]
+#[available_gas(300000)]
+fn test_entry_points() {
+    let entry_points = TestContract::__entry_points();
+    assert_eq(@entry_points.len(), @19, 'Wrong entry points count');
+    // The selector of `get_plus_2`.
+    assert_eq(
+        entry_points.at(0),
+        @0xd81d9282d6205833196c90d88e91504da20610c82b92c864b334d839ff3a75,
+        'Wrong first entry point'
+    );
 }
 
 #[test]
@@ -113,6 +207,47 @@ fn test_wrapper_valid_args() {
     assert(retdata.is_empty(), 'Array not empty');
 }
 
+#[test]
+#[available_gas(30000)]
+fn test_wrapper_newtype_arg_and_output() {
+    // Single member structs are serialized as their member.
+    let mut retdata = TestContract::__external::get_doubled_amount(serialized_element(5));
+    assert_eq(@single_deserialize(ref retdata), @10, 'Wrong result');
+    assert(retdata.is_empty(), 'Array not empty');
+}
+
+#[test]
+#[available_gas(30000)]
+fn test_wrapper_usize_arg_and_output() {
+    let mut retdata = TestContract::__external::get_next_index(serialized_element(7));
+    assert_eq(@single_deserialize(ref retdata), @8, 'Wrong result');
+    assert(retdata.is_empty(), 'Array not empty');
+}
+
+#[test]
+#[available_gas(30000)]
+#[should_panic]
+fn test_wrapper_usize_arg_out_of_range() {
+    // 2 ** 32 does not fit in a `usize`.
+    TestContract::__external::get_next_index(serialized_element(0x100000000));
+}
+
+#[test]
+#[available_gas(300000)]
+fn test_recorded_wrapper_args() {
+    let mut retdata = TestContract::__external_recording::get_doubled_amount(
+        serialized_element(5)
+    );
+    assert_eq(@single_deserialize(ref retdata), @10, 'Wrong result');
+    let (keys, data) = starknet::testing::pop_log(starknet::get_contract_address()).unwrap();
+    // The selector of `get_doubled_amount`.
+    let selector = 0x2f2f3b890e651a38a20f6a388fe16402fdf6449fe2ce7aff0f46675e1c04d5d;
+    assert_eq(@keys.len(), @1, 'unexpected keys size');
+    assert_eq(keys.at(0), @selector, 'unexpected key');
+    assert_eq(@data.len(), @1, 'unexpected data size');
+    assert_eq(data.at(0), @5, 'unexpected data');
+}
+
 #[test]
 #[available_gas(20000)]
 #[should_panic]
@@ -120,6 +255,87 @@ fn test_wrapper_valid_args_out_of_gas() {
     TestContract::__external::spend_all_gas(Default::default().span());
 }
 
+#[test]
+#[available_gas(30000)]
+fn test_wrapper_non_zero_arg() {
+    let mut calldata = Default::default();
+    calldata.append(6);
+    calldata.append(3);
+    let mut retdata = TestContract::__external::divide(calldata.span());
+    assert_eq(@single_deserialize(ref retdata), @2, 'Wrong result');
+    assert(retdata.is_empty(), 'Array not empty');
+}
+
+#[test]
+#[available_gas(30000)]
+#[should_panic(expected: ('Value must be non-zero', ))]
+fn test_wrapper_zero_non_zero_arg() {
+    let mut calldata = Default::default();
+    calldata.append(6);
+    calldata.append(0);
+    TestContract::__external::divide(calldata.span());
+}
+
+#[test]
+#[available_gas(30000)]
+fn test_wrapper_ref_args_without_output() {
+    let mut calldata = Default::default();
+    calldata.append(3);
+    calldata.append(5);
+    // Only the values of the `ref` arguments are serialized into the output.
+    let mut retdata = TestContract::__external::double_in_place(calldata.span());
+    assert_eq(@single_deserialize::<u128>(ref retdata), @6, 'Wrong value');
+    assert_eq(@single_deserialize(ref retdata), @10, 'Wrong amount');
+    assert(retdata.is_empty(), 'Array not empty');
+}
+
+#[test]
+#[available_gas(200000)]
+fn test_wrapper_view_external() {
+    TestContract::__external::set_value(serialized_element(4));
+    let mut retdata = TestContract::__external::sync_value(Default::default().span());
+    assert_eq(@single_deserialize(ref retdata), @4, 'Wrong value');
+    assert(retdata.is_empty(), 'Array not empty');
+    // The function itself is still callable with a snapshot of the state.
+    let state = TestContract::contract_state_for_testing();
+    assert_eq(@TestContract::sync_value(@state), @4, 'Wrong direct value');
+}
+
+#[test]
+#[available_gas(200000)]
+fn test_wrapper_ref_array_arg() {
+    let mut calldata = Default::default();
+    calldata.append(2);
+    calldata.append(1);
+    calldata.append(2);
+    calldata.append(3);
+    // The modified array is serialized back with its new length prefix.
+    let mut retdata = TestContract::__external::append_in_place(calldata.span());
+    assert_eq(@retdata.len(), @4, 'Wrong output length');
+    assert_eq(@single_deserialize(ref retdata), @3, 'Wrong length');
+    assert_eq(@single_deserialize(ref retdata), @1, 'Wrong first value');
+    assert_eq(@single_deserialize(ref retdata), @2, 'Wrong second value');
+    assert_eq(@single_deserialize(ref retdata), @3, 'Wrong appended value');
+}
+
+#[test]
+#[available_gas(200000)]
+fn test_wrapper_returns_optional() {
+    // A zero return value is serialized as `None`.
+    let mut retdata = TestContract::__external::get_value_if_set(Default::default().span());
+    assert(single_deserialize::<Option<felt252>>(ref retdata).is_none(), 'Expected None');
+    assert(retdata.is_empty(), 'Array not empty');
+
+    TestContract::__external::set_value(serialized_element(7));
+    let mut retdata = TestContract::__external::get_value_if_set(Default::default().span());
+    assert_eq(
+        @single_deserialize::<Option<felt252>>(ref retdata).expect('Expected Some'),
+        @7,
+        'Wrong value'
+    );
+    assert(retdata.is_empty(), 'Array not empty');
+}
+
 #[test]
 #[available_gas(200000)]
 fn test_wrapper_array_arg_and_output() {
@@ -133,6 +349,16 @@ fn test_wrapper_array_arg_and_output() {
     assert(retdata.is_empty(), 'Array not empty');
 }
 
+#[test]
+#[available_gas(200000)]
+fn test_wrapper_contract_address_array_output() {
+    let mut retdata = TestContract::__external::get_holders(Default::default().span());
+    assert_eq(@retdata.len(), @3, 'Wrong output length');
+    assert_eq(@single_deserialize(ref retdata), @2, 'Wrong length');
+    assert_eq(@single_deserialize(ref retdata), @0x10, 'Wrong first holder');
+    assert_eq(@single_deserialize(ref retdata), @0x20, 'Wrong second holder');
+}
+
 #[test]
 #[available_gas(200000)]
 fn read_first_value() {
@@ -433,3 +659,610 @@ fn test_dispatcher_serde() {
         .unwrap();
     assert(contract1.class_hash == class_hash, 'Deserialize to Dispatcher');
 }
+
+#[starknet::contract]
+mod OwnedContract {
+    use starknet::ContractAddress;
+
+    #[storage]
+    struct Storage {
+        owner: ContractAddress,
+        value: felt252,
+    }
+
+    #[constructor]
+    fn constructor(ref self: ContractState, owner: ContractAddress) {
+        self.owner.write(owner);
+    }
+
+    #[external(v0)]
+    #[only_owner]
+    fn set_value(ref self: ContractState, value: felt252) {
+        self.value.write(value);
+    }
+}
+
+#[test]
+#[available_gas(300000)]
+fn test_only_owner_wrapper_owner_caller() {
+    let owner = starknet::contract_address_const::<0x1234>();
+    OwnedContract::__constructor::constructor(serialized_element(owner));
+    starknet::testing::set_caller_address(owner);
+    OwnedContract::__external::set_value(serialized_element(1));
+}
+
+#[test]
+#[available_gas(300000)]
+#[should_panic(expected: ('Not owner', ))]
+fn test_only_owner_wrapper_other_caller() {
+    let owner = starknet::contract_address_const::<0x1234>();
+    OwnedContract::__constructor::constructor(serialized_element(owner));
+    starknet::testing::set_caller_address(starknet::contract_address_const::<0x5678>());
+    OwnedContract::__external::set_value(serialized_element(1));
+}
+
+#[starknet::contract]
+#[supports(0x3f918d17e5ee77373b56385708f855659a07f75997f365cf87748628532a055)]
+#[supports('ERC20')]
+mod IntrospectedContract {
+    #[storage]
+    struct Storage {}
+}
+
+#[test]
+#[available_gas(300000)]
+fn test_supports_interface() {
+    let mut retdata = IntrospectedContract::__external::supports_interface(
+        serialized_element('ERC20')
+    );
+    assert(single_deserialize::<bool>(ref retdata), 'ERC20 not supported');
+    let mut retdata = IntrospectedContract::__external::supports_interface(
+        serialized_element('ERC721')
+    );
+    assert(!single_deserialize::<bool>(ref retdata), 'ERC721 supported');
+}
+
+#[starknet::contract]
+mod NestedSerdeContract {
+    use array::ArrayTrait;
+    use option::OptionTrait;
+
+    #[storage]
+    struct Storage {}
+
+    #[external(v0)]
+    fn swap_optional(
+        self: @ContractState, pair: (Option::<felt252>, u128)
+    ) -> (u128, Option::<felt252>) {
+        let (optional, value) = pair;
+        (value, optional)
+    }
+
+    #[external(v0)]
+    fn count_some(self: @ContractState, mut values: Array::<Option::<felt252>>) -> usize {
+        let mut count = 0;
+        loop {
+            match values.pop_front() {
+                Option::Some(value) => {
+                    if value.is_some() {
+                        count += 1;
+                    }
+                },
+                Option::None(()) => {
+                    break count;
+                },
+            };
+        }
+    }
+}
+
+#[test]
+#[available_gas(300000)]
+fn test_wrapper_nested_option_in_tuple() {
+    let mut retdata = NestedSerdeContract::__external::swap_optional(
+        serialized_element((Option::<felt252>::Some(5), 7_u128))
+    );
+    let (value, optional) = single_deserialize::<(u128, Option::<felt252>)>(ref retdata);
+    assert_eq(@value, @7, 'Wrong value');
+    assert_eq(@optional.unwrap(), @5, 'Wrong optional');
+    assert(retdata.is_empty(), 'Array not empty');
+}
+
+#[test]
+#[available_gas(3000000)]
+fn test_wrapper_nested_option_in_array() {
+    let mut values = ArrayTrait::new();
+    values.append(Option::Some(1));
+    values.append(Option::None(()));
+    values.append(Option::Some(3));
+    let mut retdata = NestedSerdeContract::__external::count_some(serialized_element(values));
+    assert_eq(@single_deserialize::<usize>(ref retdata), @2, 'Wrong count');
+    assert(retdata.is_empty(), 'Array not empty');
+}
+
+#[derive(Drop, Serde, PartialEq)]
+enum AccountStatus {
+    Active: felt252,
+    Frozen: (),
+}
+
+#[starknet::contract]
+mod AccountStatusContract {
+    use super::AccountStatus;
+
+    #[storage]
+    struct Storage {}
+
+    #[external(v0)]
+    fn status(self: @ContractState, frozen: bool) -> AccountStatus {
+        if frozen {
+            AccountStatus::Frozen(())
+        } else {
+            AccountStatus::Active(7)
+        }
+    }
+}
+
+#[test]
+#[available_gas(300000)]
+fn test_wrapper_enum_output() {
+    let mut retdata = AccountStatusContract::__external::status(serialized_element(false));
+    assert_eq(@single_deserialize::<felt252>(ref retdata), @0, 'Wrong active index');
+    assert_eq(@single_deserialize::<felt252>(ref retdata), @7, 'Wrong active payload');
+    assert(retdata.is_empty(), 'Array not empty');
+    let mut retdata = AccountStatusContract::__external::status(serialized_element(true));
+    assert(
+        single_deserialize::<AccountStatus>(ref retdata) == AccountStatus::Frozen(()),
+        'Wrong frozen status'
+    );
+}
+
+#[starknet::contract]
+#[abi_impl]
+mod InternalCallContract {
+    #[storage]
+    struct Storage {
+        value: felt252,
+    }
+
+    #[constructor]
+    fn constructor(ref self: ContractState, value: felt252) {
+        self.value.write(value);
+    }
+
+    #[external(v0)]
+    fn get_value(self: @ContractState) -> felt252 {
+        self.value.read()
+    }
+
+    #[external(v0)]
+    fn get_double_value(self: @ContractState) -> felt252 {
+        // Entry points may be called internally as methods of the ABI trait.
+        2 * self.get_value()
+    }
+}
+
+#[test]
+#[available_gas(300000)]
+fn test_internal_call_through_abi_trait() {
+    InternalCallContract::__constructor::constructor(serialized_element(21));
+    let mut retdata = InternalCallContract::__external::get_double_value(
+        ArrayTrait::new().span()
+    );
+    assert_eq(@single_deserialize::<felt252>(ref retdata), @42, 'Wrong double value');
+}
+
+#[starknet::contract]
+#[calldata_structs]
+mod CalldataStructContract {
+    use array::ArrayTrait;
+
+    #[storage]
+    struct Storage {}
+
+    #[external(v0)]
+    fn push_sum(self: @ContractState, ref values: Array<u128>, a: u128, b: @u128) -> u128 {
+        let sum = a + *b;
+        values.append(sum);
+        sum
+    }
+}
+
+#[test]
+#[available_gas(300000)]
+fn test_calldata_struct() {
+    let mut values = ArrayTrait::new();
+    values.append(1_u128);
+    let calldata = CalldataStructContract::__calldata::PushSumCalldata { values, a: 2, b: 3 };
+    let mut data = ArrayTrait::new();
+    serde::Serde::serialize(@calldata, ref data);
+    let mut retdata = CalldataStructContract::__external::push_sum(data.span());
+    let values = single_deserialize::<Array<u128>>(ref retdata);
+    assert_eq(@values.len(), @2, 'Wrong values length');
+    assert_eq(values.at(1), @5, 'Wrong appended value');
+    assert_eq(@single_deserialize::<u128>(ref retdata), @5, 'Wrong sum');
+    assert(retdata.is_empty(), 'Array not empty');
+}
+
+#[starknet::contract]
+#[abi_impl]
+mod InterfacesContract {
+    #[storage]
+    struct Storage {
+        balance: u128,
+    }
+
+    #[external(v0)]
+    #[interface(IBalance)]
+    fn get_balance(self: @ContractState) -> u128 {
+        self.balance.read()
+    }
+
+    #[external(v0)]
+    #[interface(IBalance)]
+    fn deposit(ref self: ContractState, amount: u128) {
+        self.balance.write(self.get_balance() + amount);
+    }
+
+    #[external(v0)]
+    fn get_doubled_balance(self: @ContractState) -> u128 {
+        // Entry points of an interface are methods of its ABI trait.
+        2 * self.get_balance()
+    }
+}
+
+#[test]
+#[available_gas(300000)]
+fn test_interface_abi_traits() {
+    InterfacesContract::__external::deposit(serialized_element(21_u128));
+    let mut retdata = InterfacesContract::__external::get_doubled_balance(
+        ArrayTrait::new().span()
+    );
+    assert_eq(@single_deserialize::<u128>(ref retdata), @42, 'Wrong doubled balance');
+}
+
+#[starknet::contract]
+mod StrictArgsContract {
+    use array::{ArrayTrait, SpanTrait};
+
+    #[storage]
+    struct Storage {}
+
+    #[external(v0)]
+    #[strict_args]
+    fn sum(self: @ContractState, values: Array<felt252>, offset: felt252) -> felt252 {
+        let mut sum = offset;
+        let mut values = values.span();
+        loop {
+            match values.pop_front() {
+                Option::Some(value) => {
+                    sum += *value;
+                },
+                Option::None(()) => {
+                    break sum;
+                },
+            };
+        }
+    }
+}
+
+#[test]
+#[available_gas(300000)]
+fn test_strict_args_valid_calldata() {
+    let mut calldata = Default::default();
+    calldata.append(2);
+    calldata.append(1);
+    calldata.append(2);
+    calldata.append(10);
+    let mut retdata = StrictArgsContract::__external::sum(calldata.span());
+    assert_eq(@single_deserialize(ref retdata), @13, 'Wrong sum');
+}
+
+#[test]
+#[available_gas(300000)]
+#[should_panic(expected: ('Bad calldata length after arg', 'values', ))]
+fn test_strict_args_wrong_array_length() {
+    // The length prefix of `values` is one short, so its last element is taken as `offset`, and
+    // `offset` as trailing data.
+    let mut calldata = Default::default();
+    calldata.append(1);
+    calldata.append(1);
+    calldata.append(2);
+    calldata.append(10);
+    StrictArgsContract::__external::sum(calldata.span());
+}
+
+#[starknet::contract]
+#[contract_name_const]
+mod NamedContract {
+    #[storage]
+    struct Storage {}
+
+    #[external(v0)]
+    fn name(self: @ContractState) -> felt252 {
+        __CONTRACT_NAME
+    }
+}
+
+#[test]
+#[available_gas(300000)]
+fn test_contract_name_const() {
+    let mut retdata = NamedContract::__external::name(ArrayTrait::new().span());
+    assert_eq(@single_deserialize(ref retdata), @'NamedContract', 'Wrong contract name');
+}
+
+#[starknet::contract]
+mod TypeAliasContract {
+    type Balance = u256;
+
+    #[storage]
+    struct Storage {
+        balance: Balance,
+    }
+
+    #[external(v0)]
+    fn set_balance(ref self: ContractState, balance: Balance) {
+        self.balance.write(balance);
+    }
+
+    #[external(v0)]
+    fn get_balance(self: @ContractState) -> Balance {
+        self.balance.read()
+    }
+}
+
+#[test]
+#[available_gas(300000)]
+fn test_type_alias_entry_point_types() {
+    TypeAliasContract::__external::set_balance(serialized_element(u256 { low: 1, high: 2 }));
+    let mut retdata = TypeAliasContract::__external::get_balance(ArrayTrait::new().span());
+    assert_eq(
+        @single_deserialize::<u256>(ref retdata), @u256 { low: 1, high: 2 }, 'Wrong balance'
+    );
+}
+
+#[starknet::contract]
+mod LimbOrderContract {
+    #[storage]
+    struct Storage {}
+
+    #[external(v0)]
+    fn split(self: @ContractState, value: u256) -> (u128, u128) {
+        (value.low, value.high)
+    }
+
+    #[external(v0)]
+    fn join(self: @ContractState, low: u128, high: u128) -> u256 {
+        u256 { low, high }
+    }
+}
+
+#[test]
+#[available_gas(300000)]
+fn test_u256_calldata_limb_order() {
+    // A u256 argument is read from the calldata as `[low, high]`.
+    let mut calldata = Default::default();
+    calldata.append(5);
+    calldata.append(7);
+    let mut retdata = LimbOrderContract::__external::split(calldata.span());
+    assert_eq(retdata.pop_front().unwrap(), @5, 'Wrong low limb');
+    assert_eq(retdata.pop_front().unwrap(), @7, 'Wrong high limb');
+    assert(retdata.is_empty(), 'Array not empty');
+}
+
+#[test]
+#[available_gas(300000)]
+fn test_u256_retdata_limb_order() {
+    // A returned u256 is written to the return data as `[low, high]`.
+    let mut calldata = Default::default();
+    calldata.append(5);
+    calldata.append(7);
+    let mut retdata = LimbOrderContract::__external::join(calldata.span());
+    assert_eq(retdata.pop_front().unwrap(), @5, 'Wrong low limb');
+    assert_eq(retdata.pop_front().unwrap(), @7, 'Wrong high limb');
+    assert(retdata.is_empty(), 'Array not empty');
+}
+
+#[starknet::contract]
+mod PointContract {
+    #[storage]
+    struct Storage {}
+
+    #[external(v0)]
+    fn swap(self: @ContractState, point: (felt252, felt252)) -> (felt252, felt252) {
+        let (x, y) = point;
+        (y, x)
+    }
+}
+
+#[test]
+#[available_gas(300000)]
+fn test_felt252_pair_roundtrip() {
+    // A pair is read from the calldata, and written to the return data, as `[first, second]`.
+    let mut calldata = Default::default();
+    calldata.append(3);
+    calldata.append(4);
+    let mut retdata = PointContract::__external::swap(calldata.span());
+    assert_eq(retdata.pop_front().unwrap(), @4, 'Wrong first element');
+    assert_eq(retdata.pop_front().unwrap(), @3, 'Wrong second element');
+    assert(retdata.is_empty(), 'Array not empty');
+}
+
+#[test]
+#[available_gas(300000)]
+#[should_panic(expected: ('Input too short for arguments', ))]
+fn test_felt252_pair_missing_element() {
+    let mut calldata = Default::default();
+    calldata.append(3);
+    PointContract::__external::swap(calldata.span());
+}
+
+#[starknet::contract]
+mod Secp256k1Contract {
+    use starknet::secp256_trait::Secp256PointTrait;
+    use starknet::secp256k1::{Secp256k1Point, Secp256k1PointImpl};
+
+    #[storage]
+    struct Storage {}
+
+    #[external(v0)]
+    fn get_coordinates(self: @ContractState, point: Secp256k1Point) -> (u256, u256) {
+        point.get_coordinates().unwrap_syscall()
+    }
+}
+
+#[test]
+#[available_gas(100000000)]
+fn test_wrapper_secp256k1_point_arg() {
+    // The generator point of the curve.
+    let x = 0x79be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798_u256;
+    let y = 0x483ada7726a3c4655da4fbfc0e1108a8fd17b448a68554199c47d08ffb10d4b8_u256;
+    let mut calldata = Default::default();
+    x.serialize(ref calldata);
+    y.serialize(ref calldata);
+    let mut retdata = Secp256k1Contract::__external::get_coordinates(calldata.span());
+    assert_eq(@single_deserialize(ref retdata), @x, 'Wrong x');
+    assert_eq(@single_deserialize(ref retdata), @y, 'Wrong y');
+    assert(retdata.is_empty(), 'Array not empty');
+}
+
+#[test]
+#[available_gas(100000000)]
+#[should_panic(expected: ('Point must be on the curve', ))]
+fn test_wrapper_secp256k1_point_arg_not_on_curve() {
+    let mut calldata = Default::default();
+    1_u256.serialize(ref calldata);
+    1_u256.serialize(ref calldata);
+    Secp256k1Contract::__external::get_coordinates(calldata.span());
+}
+
+#[test]
+#[available_gas(100000000)]
+#[should_panic(expected: ('Input too short for arguments', ))]
+fn test_wrapper_secp256k1_point_arg_missing_coordinate() {
+    let mut calldata = Default::default();
+    1_u256.serialize(ref calldata);
+    Secp256k1Contract::__external::get_coordinates(calldata.span());
+}
+
+#[starknet::contract]
+mod ExecutionInfoContract {
+    use box::BoxTrait;
+    use starknet::info::ExecutionInfo;
+
+    #[storage]
+    struct Storage {}
+
+    #[external(v0)]
+    #[inject_execution_info]
+    fn delayed_timestamp(self: @ContractState, info: ExecutionInfo, delay: u64) -> u64 {
+        info.block_info.unbox().block_timestamp + delay
+    }
+}
+
+#[test]
+#[available_gas(300000)]
+fn test_inject_execution_info() {
+    starknet::testing::set_block_timestamp(10_u64);
+    // Only the delay is read from the calldata.
+    let mut calldata = Default::default();
+    calldata.append(5);
+    let mut retdata = ExecutionInfoContract::__external::delayed_timestamp(calldata.span());
+    assert_eq(retdata.pop_front().unwrap(), @15, 'Wrong timestamp');
+    assert(retdata.is_empty(), 'Array not empty');
+}
+
+#[starknet::contract]
+mod RestArgsContract {
+    use array::ArrayTrait;
+    use option::OptionTrait;
+
+    #[storage]
+    struct Storage {}
+
+    #[external(v0)]
+    #[rest_args]
+    fn sum(self: @ContractState, first: felt252, mut rest: Array::<felt252>) -> felt252 {
+        let mut total = first;
+        loop {
+            match rest.pop_front() {
+                Option::Some(value) => {
+                    total += value;
+                },
+                Option::None(()) => {
+                    break ();
+                },
+            };
+        };
+        total
+    }
+}
+
+#[test]
+#[available_gas(300000)]
+fn test_wrapper_rest_args() {
+    let mut calldata = Default::default();
+    calldata.append(1);
+    calldata.append(2);
+    calldata.append(3);
+    calldata.append(4);
+    let mut retdata = RestArgsContract::__external::sum(calldata.span());
+    assert_eq(@single_deserialize(ref retdata), @10, 'Wrong result');
+    assert(retdata.is_empty(), 'Array not empty');
+}
+
+#[test]
+#[available_gas(300000)]
+fn test_wrapper_empty_rest_args() {
+    let mut retdata = RestArgsContract::__external::sum(serialized_element(1));
+    assert_eq(@single_deserialize(ref retdata), @1, 'Wrong result');
+    assert(retdata.is_empty(), 'Array not empty');
+}
+
+#[starknet::contract]
+mod TransferContract {
+    use starknet::ContractAddress;
+
+    #[storage]
+    struct Storage {}
+
+    #[event]
+    #[derive(Drop, starknet::Event)]
+    enum Event {
+        Transfer: Transfer,
+    }
+
+    #[derive(Drop, starknet::Event)]
+    struct Transfer {
+        #[key]
+        from: ContractAddress,
+        to: ContractAddress,
+        amount: u128,
+    }
+
+    #[external(v0)]
+    fn transfer(ref self: ContractState, from: ContractAddress, to: ContractAddress, amount: u128) {
+        self.emit(Event::Transfer(Transfer { from, to, amount }));
+    }
+}
+
+#[test]
+#[available_gas(300000)]
+fn test_event_name_key() {
+    let mut state = TransferContract::contract_state_for_testing();
+    TransferContract::transfer(
+        ref state,
+        starknet::contract_address_const::<1>(),
+        starknet::contract_address_const::<2>(),
+        3
+    );
+    let (keys, data) = starknet::testing::pop_log(starknet::get_contract_address()).unwrap();
+    // The event name is hashed into the first key, before the `#[key]` members.
+    let transfer_key = 0x99cd8bde557814842a3121e8ddfd433a539b8c9f14bf31ebf108d12e6196e9;
+    assert_eq(@keys.len(), @2, 'unexpected keys size');
+    assert_eq(keys.at(0), @transfer_key, 'unexpected name key');
+    assert_eq(keys.at(1), @1, 'unexpected from key');
+    assert_eq(@data.len(), @2, 'unexpected data size');
+    assert_eq(data.at(0), @2, 'unexpected to');
+    assert_eq(data.at(1), @3, 'unexpected amount');
+}
@@ -1,8 +1,8 @@
 use core::traits::Into;
 use core::result::ResultTrait;
 use test::test_utils::{assert_eq, assert_ne};
-use starknet::syscalls::{deploy_syscall, get_block_hash_syscall};
-use array::ArrayTrait;
+use starknet::syscalls::{call_contract_syscall, deploy_syscall, get_block_hash_syscall};
+use array::{ArrayTrait, SpanTrait};
 use traits::TryInto;
 use option::OptionTrait;
 use starknet::SyscallResultTrait;
@@ -14,6 +14,7 @@ trait IContract<T> {
 }
 
 #[starknet::contract]
+#[selector_consts]
 mod ContractA {
     use traits::Into;
     use starknet::info::get_contract_address;
@@ -115,6 +116,23 @@ fn test_flow_out_of_gas() {
     assert_eq(@library.foo(300), @0, 'library.foo(300) == 0');
 }
 
+#[test]
+#[available_gas(30000000)]
+fn test_call_with_selector_const() {
+    let mut calldata = Default::default();
+    calldata.append(100);
+    let (address, _) = deploy_syscall(
+        ContractA::TEST_CLASS_HASH.try_into().unwrap(), 0, calldata.span(), false
+    )
+        .unwrap();
+    let mut calldata = Default::default();
+    calldata.append(300);
+    let retdata = call_contract_syscall(address, ContractA::__selectors::FOO, calldata.span())
+        .unwrap_syscall();
+    assert_eq(@retdata.len(), @1, 'retdata.len() == 1');
+    assert_eq(retdata.at(0), @100, 'retdata[0] == 100');
+}
+
 #[test]
 #[available_gas(30000000)]
 fn test_class_hash_not_found() {
@@ -77,3 +77,64 @@ fn test_replace_flow() {
     let mut contract1 = IWithFooDispatcher { contract_address: address0 };
     assert_eq(@contract1.foo(), @100, 'contract1.foo() != 100');
 }
+
+#[starknet::interface]
+trait IUpgradeable<TContractState> {
+    fn upgrade(ref self: TContractState, new_class_hash: ClassHash);
+}
+
+#[starknet::contract]
+#[upgradeable]
+#[only_owner]
+mod UpgradeableContract {
+    use starknet::ContractAddress;
+
+    #[storage]
+    struct Storage {
+        value: u128,
+        owner: ContractAddress,
+    }
+
+    #[constructor]
+    fn constructor(ref self: ContractState, value: u128, owner: ContractAddress) {
+        self.value.write(value);
+        self.owner.write(owner);
+    }
+}
+
+fn deploy_upgradeable_contract(owner: starknet::ContractAddress) -> starknet::ContractAddress {
+    let mut calldata = Default::default();
+    calldata.append(100);
+    calldata.append(starknet::contract_address_to_felt252(owner));
+    let (address, _) = deploy_syscall(
+        class_hash: UpgradeableContract::TEST_CLASS_HASH.try_into().unwrap(),
+        contract_address_salt: 0,
+        calldata: calldata.span(),
+        deploy_from_zero: false
+    )
+        .unwrap();
+    address
+}
+
+#[test]
+#[available_gas(30000000)]
+fn test_upgrade_flow() {
+    let address = deploy_upgradeable_contract(starknet::get_contract_address());
+
+    let mut contract = IUpgradeableDispatcher { contract_address: address };
+    contract.upgrade(ContractB::TEST_CLASS_HASH.try_into().unwrap());
+
+    // The storage is kept by the upgraded contract.
+    let mut upgraded_contract = IWithFooDispatcher { contract_address: address };
+    assert_eq(@upgraded_contract.foo(), @100, 'upgraded.foo() != 100');
+}
+
+#[test]
+#[available_gas(30000000)]
+#[should_panic(expected: ('Not owner', 'ENTRYPOINT_FAILED', ))]
+fn test_upgrade_not_owner() {
+    let address = deploy_upgradeable_contract(starknet::contract_address_const::<0x1234>());
+
+    let mut contract = IUpgradeableDispatcher { contract_address: address };
+    contract.upgrade(ContractB::TEST_CLASS_HASH.try_into().unwrap());
+}
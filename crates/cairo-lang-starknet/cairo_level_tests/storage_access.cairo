@@ -2,7 +2,7 @@ use core::option::OptionTrait;
 use core::traits::{Into, TryInto};
 use array::{ArrayTrait, SpanTrait};
 use starknet::{
-    ClassHash, ContractAddress, EthAddress, StorageAddress, SyscallResult,
+    ClassHash, ContractAddress, EthAddress, StorageAddress, SyscallResult, SyscallResultTrait,
     storage_address_to_felt252, storage_address_try_from_felt252
 };
 use starknet::eth_address::Felt252TryIntoEthAddress;
@@ -89,3 +89,425 @@ fn write_read_struct() {
     assert(single_deserialize(ref retdata) == x, 'Wrong result');
     assert(retdata.is_empty(), 'Array not empty');
 }
+
+#[starknet::contract]
+mod TestU256Contract {
+    #[storage]
+    struct Storage {
+        amount: u256,
+    }
+
+    #[external(v0)]
+    fn set_amount(ref self: ContractState, value: u256) {
+        self.amount.write(value);
+    }
+
+    #[external(v0)]
+    fn get_amount(self: @ContractState) -> u256 {
+        self.amount.read()
+    }
+}
+
+#[test]
+#[available_gas(900000)]
+fn write_read_u256() {
+    let x = u256 { low: 1, high: 2 };
+    assert(
+        TestU256Contract::__external::set_amount(serialized_element(x)).is_empty(), 'Not empty'
+    );
+
+    let mut retdata = TestU256Contract::__external::get_amount(Default::default().span());
+    assert(single_deserialize(ref retdata) == x, 'Wrong result');
+    assert(retdata.is_empty(), 'Array not empty');
+
+    // The limbs are stored in two sequential slots.
+    let state = TestU256Contract::unsafe_new_contract_state();
+    let base = TestU256Contract::__storage::amount::InternalContractStateTrait::address(
+        @state.amount
+    );
+    let low = starknet::storage_read_syscall(
+        0, starknet::storage_address_from_base_and_offset(base, 0)
+    )
+        .unwrap_syscall();
+    let high = starknet::storage_read_syscall(
+        0, starknet::storage_address_from_base_and_offset(base, 1)
+    )
+        .unwrap_syscall();
+    assert(low == 1, 'Wrong low limb');
+    assert(high == 2, 'Wrong high limb');
+}
+
+#[starknet::contract]
+mod TestResettableContract {
+    #[storage]
+    #[resettable]
+    struct Storage {
+        amount: u256,
+        flags: LegacyMap::<felt252, bool>,
+    }
+
+    #[external(v0)]
+    fn set_amount(ref self: ContractState, value: u256) {
+        self.amount.write(value);
+    }
+
+    #[external(v0)]
+    fn get_amount(self: @ContractState) -> u256 {
+        self.amount.read()
+    }
+
+    #[external(v0)]
+    fn set_flag(ref self: ContractState, key: felt252) {
+        self.flags.write(key, true);
+    }
+
+    #[external(v0)]
+    fn get_flag(self: @ContractState, key: felt252) -> bool {
+        self.flags.read(key)
+    }
+}
+
+#[test]
+#[available_gas(900000)]
+fn reset_storage() {
+    let mut state = TestResettableContract::contract_state_for_testing();
+    TestResettableContract::set_amount(ref state, u256 { low: 1, high: 2 });
+    TestResettableContract::set_flag(ref state, 3);
+
+    TestResettableContract::__reset_storage(ref state);
+    assert(TestResettableContract::get_amount(@state) == 0, 'Amount not reset');
+    // Mappings are not reset.
+    assert(TestResettableContract::get_flag(@state, 3), 'Flag reset');
+}
+
+#[starknet::contract]
+mod TestPoseidonMappingContract {
+    #[storage]
+    struct Storage {
+        #[hash(poseidon)]
+        balances: LegacyMap::<(felt252, u8), u256>,
+    }
+
+    #[external(v0)]
+    fn set_balance(ref self: ContractState, key: (felt252, u8), value: u256) {
+        self.balances.write(key, value);
+    }
+
+    #[external(v0)]
+    fn get_balance(self: @ContractState, key: (felt252, u8)) -> u256 {
+        self.balances.read(key)
+    }
+}
+
+#[test]
+#[available_gas(900000)]
+fn write_read_poseidon_mapping() {
+    let mut state = TestPoseidonMappingContract::contract_state_for_testing();
+    TestPoseidonMappingContract::set_balance(ref state, (1, 2), 3);
+    TestPoseidonMappingContract::set_balance(ref state, (1, 3), 4);
+    assert(TestPoseidonMappingContract::get_balance(@state, (1, 2)) == 3, 'Wrong balance');
+    assert(TestPoseidonMappingContract::get_balance(@state, (1, 3)) == 4, 'Wrong balance');
+    assert(TestPoseidonMappingContract::get_balance(@state, (2, 2)) == 0, 'Wrong balance');
+}
+
+#[starknet::contract]
+mod TestPinnedSlotContract {
+    #[storage]
+    struct Storage {
+        #[storage_slot(0x1234)]
+        pinned: felt252,
+        derived: felt252,
+    }
+}
+
+#[test]
+#[available_gas(900000)]
+fn write_pinned_slot() {
+    let mut state = TestPinnedSlotContract::unsafe_new_contract_state();
+    TestPinnedSlotContract::__storage::pinned::InternalContractStateTrait::write(
+        ref state.pinned, 5
+    );
+    let value = starknet::storage_read_syscall(
+        0, starknet::storage_address_try_from_felt252(0x1234).unwrap()
+    )
+        .unwrap_syscall();
+    assert(value == 5, 'Wrong pinned slot');
+}
+
+#[starknet::contract]
+mod TestReadFailureContract {
+    #[storage]
+    struct Storage {
+        #[read_failure(panic)]
+        value: u128,
+    }
+
+    #[external(v0)]
+    fn set_value(ref self: ContractState, value: u128) {
+        self.value.write(value);
+    }
+
+    #[external(v0)]
+    fn get_value(self: @ContractState) -> u128 {
+        self.value.read()
+    }
+}
+
+#[test]
+#[available_gas(900000)]
+fn write_read_panicking_on_read_failure() {
+    let mut state = TestReadFailureContract::contract_state_for_testing();
+    TestReadFailureContract::set_value(ref state, 5);
+    assert(TestReadFailureContract::get_value(@state) == 5, 'Wrong value');
+}
+
+#[derive(Copy, Drop, PartialEq, Serde, storage_access::StorageAccess)]
+enum Status {
+    Pending: (),
+    Active: (),
+    Closed: (),
+}
+
+#[derive(Copy, Drop, PartialEq, Serde, storage_access::StorageAccess)]
+enum Limit {
+    Unlimited: (),
+    Amount: u256,
+}
+
+#[starknet::contract]
+mod TestEnumContract {
+    use super::{Limit, Status};
+
+    #[storage]
+    struct Storage {
+        status: Status,
+        limit: Limit,
+    }
+
+    #[external(v0)]
+    fn set_status(ref self: ContractState, value: Status) {
+        self.status.write(value);
+    }
+
+    #[external(v0)]
+    fn get_status(self: @ContractState) -> Status {
+        self.status.read()
+    }
+
+    #[external(v0)]
+    fn set_limit(ref self: ContractState, value: Limit) {
+        self.limit.write(value);
+    }
+
+    #[external(v0)]
+    fn get_limit(self: @ContractState) -> Limit {
+        self.limit.read()
+    }
+}
+
+#[test]
+#[available_gas(900000)]
+fn write_read_fieldless_enum() {
+    let mut state = TestEnumContract::contract_state_for_testing();
+    // Unwritten enums read as their first variant.
+    assert(TestEnumContract::get_status(@state) == Status::Pending(()), 'Wrong initial status');
+    TestEnumContract::set_status(ref state, Status::Closed(()));
+    assert(TestEnumContract::get_status(@state) == Status::Closed(()), 'Wrong status');
+}
+
+#[test]
+#[available_gas(900000)]
+fn write_read_enum_with_payload() {
+    let mut state = TestEnumContract::contract_state_for_testing();
+    TestEnumContract::set_limit(ref state, Limit::Amount(u256 { low: 1, high: 2 }));
+    assert(
+        TestEnumContract::get_limit(@state) == Limit::Amount(u256 { low: 1, high: 2 }),
+        'Wrong limit'
+    );
+    TestEnumContract::set_limit(ref state, Limit::Unlimited(()));
+    assert(TestEnumContract::get_limit(@state) == Limit::Unlimited(()), 'Wrong unlimited limit');
+}
+
+#[starknet::contract]
+mod TestMemberNamedAsModuleContract {
+    #[storage]
+    struct Storage {
+        token: felt252,
+    }
+
+    mod token {
+        fn scale(value: felt252) -> felt252 {
+            value * 100
+        }
+    }
+
+    #[external(v0)]
+    fn set_token(ref self: ContractState, value: felt252) {
+        self.token.write(token::scale(value));
+    }
+
+    #[external(v0)]
+    fn get_token(self: @ContractState) -> felt252 {
+        self.token.read()
+    }
+}
+
+#[test]
+#[available_gas(900000)]
+fn write_read_member_named_as_module() {
+    let mut state = TestMemberNamedAsModuleContract::contract_state_for_testing();
+    TestMemberNamedAsModuleContract::set_token(ref state, 3);
+    assert(TestMemberNamedAsModuleContract::get_token(@state) == 300, 'Wrong token');
+}
+
+#[starknet::contract]
+mod TestFlatContract {
+    #[flat]
+    struct Limits {
+        min: u128,
+        max: u128,
+    }
+
+    #[flat]
+    struct Config {
+        admin: felt252,
+        limits: Limits,
+    }
+
+    #[storage]
+    #[resettable]
+    struct Storage {
+        config: Config,
+        fallback: Config,
+    }
+
+    #[external(v0)]
+    fn set_config(ref self: ContractState, admin: felt252, min: u128, max: u128) {
+        self.config.admin.write(admin);
+        self.config.limits.min.write(min);
+        self.config.limits.max.write(max);
+    }
+
+    #[external(v0)]
+    fn get_config(self: @ContractState) -> (felt252, u128, u128) {
+        (self.config.admin.read(), self.config.limits.min.read(), self.config.limits.max.read())
+    }
+
+    #[external(v0)]
+    fn get_fallback_max(self: @ContractState) -> u128 {
+        self.fallback.limits.max.read()
+    }
+}
+
+#[test]
+#[available_gas(900000)]
+fn write_read_flat_struct() {
+    let mut state = TestFlatContract::contract_state_for_testing();
+    TestFlatContract::set_config(ref state, 'admin', 1, 2);
+    let (admin, min, max) = TestFlatContract::get_config(@state);
+    assert(admin == 'admin', 'Wrong admin');
+    assert(min == 1, 'Wrong min');
+    assert(max == 2, 'Wrong max');
+    // The fields of different members are stored at different addresses.
+    assert(TestFlatContract::get_fallback_max(@state) == 0, 'Wrong fallback');
+
+    TestFlatContract::__reset_storage(ref state);
+    let (admin, min, max) = TestFlatContract::get_config(@state);
+    assert(admin == 0 && min == 0 && max == 0, 'Config not reset');
+}
+
+#[starknet::contract]
+mod TestMigrateContract {
+    struct OldStorage {
+        #[storage_slot(0x1234)]
+        balance: u128,
+        owner: felt252,
+        approvals: LegacyMap::<felt252, bool>,
+    }
+
+    #[storage]
+    #[migrate_from(OldStorage)]
+    struct Storage {
+        balance: u128,
+        owner: felt252,
+        approvals: LegacyMap::<felt252, bool>,
+    }
+
+    #[external(v0)]
+    fn get_balance(self: @ContractState) -> u128 {
+        self.balance.read()
+    }
+}
+
+#[test]
+#[available_gas(900000)]
+fn migrate_storage() {
+    let mut state = TestMigrateContract::contract_state_for_testing();
+    starknet::storage_write_syscall(
+        0, starknet::storage_address_try_from_felt252(0x1234).unwrap(), 7
+    )
+        .unwrap_syscall();
+    assert(TestMigrateContract::get_balance(@state) == 0, 'Balance before migration');
+    TestMigrateContract::__migrate(ref state);
+    assert(TestMigrateContract::get_balance(@state) == 7, 'Balance not migrated');
+}
+
+#[starknet::contract]
+mod TestOptionalReadsContract {
+    #[storage]
+    struct Storage {
+        #[optional_reads]
+        balances: LegacyMap::<felt252, u256>,
+    }
+
+    #[external(v0)]
+    fn set_balance(ref self: ContractState, key: felt252, value: u256) {
+        self.balances.write(key, value);
+    }
+
+    #[external(v0)]
+    fn find_balance(self: @ContractState, key: felt252) -> Option<u256> {
+        self.balances.read_optional(key)
+    }
+}
+
+#[test]
+#[available_gas(900000)]
+fn read_optional_mapping() {
+    let mut state = TestOptionalReadsContract::contract_state_for_testing();
+    assert(TestOptionalReadsContract::find_balance(@state, 1).is_none(), 'Absent entry found');
+    TestOptionalReadsContract::set_balance(ref state, 1, 3);
+    assert(TestOptionalReadsContract::find_balance(@state, 1).unwrap() == 3, 'Wrong balance');
+    // A zero value can't be told apart from an absent entry.
+    TestOptionalReadsContract::set_balance(ref state, 1, 0);
+    assert(TestOptionalReadsContract::find_balance(@state, 1).is_none(), 'Zero entry found');
+}
+
+#[starknet::contract]
+mod TestTupleContract {
+    #[storage]
+    struct Storage {
+        point: (felt252, felt252),
+        after_point: felt252,
+    }
+
+    #[external(v0)]
+    fn set_point(ref self: ContractState, value: (felt252, felt252)) {
+        self.point.write(value);
+    }
+
+    #[external(v0)]
+    fn get_point(self: @ContractState) -> (felt252, felt252) {
+        self.point.read()
+    }
+}
+
+#[test]
+#[available_gas(900000)]
+fn write_read_tuple() {
+    let mut state = TestTupleContract::contract_state_for_testing();
+    TestTupleContract::set_point(ref state, (3, 4));
+    let (x, y) = TestTupleContract::get_point(@state);
+    assert(x == 3, 'Wrong first component');
+    assert(y == 4, 'Wrong second component');
+}
@@ -5,6 +5,7 @@ use cairo_lang_defs::ids::{
     SubmoduleId, TopLevelLanguageElementId, TraitFunctionId, TraitId,
 };
 use cairo_lang_diagnostics::{DiagnosticAdded, Maybe};
+use cairo_lang_semantic::corelib::{core_felt252_ty, get_core_ty_by_name};
 use cairo_lang_semantic::db::SemanticGroup;
 use cairo_lang_semantic::items::attribute::SemanticQueryAttrs;
 use cairo_lang_semantic::items::enm::SemanticEnumEx;
@@ -20,10 +21,12 @@ use serde::{Deserialize, Serialize};
 use smol_str::SmolStr;
 use thiserror::Error;
 
+use crate::contract::selector_name_from_attr;
 use crate::plugin::aux_data::StarkNetEventAuxData;
 use crate::plugin::consts::{
     CONSTRUCTOR_ATTR, CONTRACT_STATE_NAME, EVENT_ATTR, EVENT_TYPE_NAME, EXTERNAL_ATTR,
-    INTERFACE_ATTR, L1_HANDLER_ATTR,
+    INJECT_EXECUTION_INFO_ATTR, INTERFACE_ATTR, L1_HANDLER_ATTR, REST_ARGS_ATTR,
+    RETURNS_OPTIONAL_ATTR, SELECTOR_NAME_ATTR, VIEW_EXTERNAL_ATTR,
 };
 use crate::plugin::events::{EventData, EventFieldKind};
 
@@ -119,6 +122,9 @@ impl AbiBuilder {
                 continue;
             }
 
+            // Handle external functions of non-external impls.
+            builder.add_impl_external_functions(db, impl_id, storage_type)?;
+
             // Handle impls of starknet::Event.
             // Check if we have an Event derive plugin data on the impl.
             let module_file = impl_id.module_file_id(db.upcast());
@@ -247,6 +253,26 @@ impl AbiBuilder {
         Ok(())
     }
 
+    /// Adds the functions of a non-external impl that are marked as `#[external]` to the ABI as
+    /// external functions.
+    fn add_impl_external_functions(
+        &mut self,
+        db: &dyn SemanticGroup,
+        impl_def_id: ImplDefId,
+        storage_type: TypeId,
+    ) -> Result<(), ABIError> {
+        for function in db.impl_functions(impl_def_id).unwrap_or_default().values() {
+            if !function.has_attr(db, EXTERNAL_ATTR).map_err(|_| ABIError::CompilationError)? {
+                continue;
+            }
+            let function_abi =
+                Item::Function(self.impl_function_as_abi(db, *function, storage_type)?);
+            self.abi.items.push(function_abi);
+        }
+
+        Ok(())
+    }
+
     /// Adds an impl to the ABI.
     fn add_impl(
         &mut self,
@@ -282,14 +308,50 @@ impl AbiBuilder {
         free_function_id: FreeFunctionId,
         storage_type: TypeId,
     ) -> Result<(), ABIError> {
-        let name: String = free_function_id.name(db.upcast()).into();
-        let signature =
+        // Entry points are named in the ABI by the strings hashed into their selectors.
+        let name: String = free_function_id
+            .find_attr(db, SELECTOR_NAME_ATTR)
+            .map_err(|_| ABIError::CompilationError)?
+            .and_then(|attr| selector_name_from_attr(db.upcast(), &attr))
+            .unwrap_or_else(|| free_function_id.name(db.upcast()))
+            .into();
+        let mut signature =
             db.free_function_signature(free_function_id).map_err(|_| ABIError::CompilationError)?;
+        // The injected execution info is not part of the calldata.
+        if free_function_id
+            .has_attr(db, INJECT_EXECUTION_INFO_ATTR)
+            .map_err(|_| ABIError::CompilationError)?
+            && signature.params.len() > 1
+        {
+            signature.params.remove(1);
+        }
 
-        let (inputs, state_mutability) =
+        let (mut inputs, mut state_mutability) =
             self.get_function_signature_inputs_and_mutability(&signature, storage_type, db)?;
+        if free_function_id.has_attr(db, REST_ARGS_ATTR).map_err(|_| ABIError::CompilationError)? {
+            mark_rest_input(&mut inputs);
+        }
+        if free_function_id
+            .has_attr(db, VIEW_EXTERNAL_ATTR)
+            .map_err(|_| ABIError::CompilationError)?
+        {
+            state_mutability = StateMutability::External;
+        }
 
-        let outputs = self.get_signature_outputs(db, &signature)?;
+        let outputs = if free_function_id
+            .has_attr(db, RETURNS_OPTIONAL_ATTR)
+            .map_err(|_| ABIError::CompilationError)?
+        {
+            let ty = get_core_ty_by_name(
+                db,
+                "Option".into(),
+                vec![GenericArgumentId::Type(core_felt252_ty(db))],
+            );
+            self.add_type(db, ty)?;
+            vec![Output { ty: ty.format(db) }]
+        } else {
+            self.get_signature_outputs(db, &signature)?
+        };
 
         self.abi.items.push(Item::Function(Function { name, inputs, outputs, state_mutability }));
 
@@ -307,11 +369,14 @@ impl AbiBuilder {
         let signature =
             db.free_function_signature(free_function_id).map_err(|_| ABIError::CompilationError)?;
 
-        let (inputs, state_mutability) =
+        let (mut inputs, state_mutability) =
             self.get_function_signature_inputs_and_mutability(&signature, storage_type, db)?;
         if state_mutability != StateMutability::External {
             return Err(ABIError::UnexpectedType);
         }
+        if free_function_id.has_attr(db, REST_ARGS_ATTR).map_err(|_| ABIError::CompilationError)? {
+            mark_rest_input(&mut inputs);
+        }
 
         self.abi.items.push(Item::Constructor(Constructor { name, inputs }));
 
@@ -329,8 +394,11 @@ impl AbiBuilder {
         let signature =
             db.free_function_signature(free_function_id).map_err(|_| ABIError::CompilationError)?;
 
-        let (inputs, state_mutability) =
+        let (mut inputs, state_mutability) =
             self.get_function_signature_inputs_and_mutability(&signature, storage_type, db)?;
+        if free_function_id.has_attr(db, REST_ARGS_ATTR).map_err(|_| ABIError::CompilationError)? {
+            mark_rest_input(&mut inputs);
+        }
 
         let outputs = self.get_signature_outputs(db, &signature)?;
 
@@ -365,8 +433,17 @@ impl AbiBuilder {
             if is_ref { StateMutability::External } else { StateMutability::View };
         let mut inputs = vec![];
         for param in params {
-            self.add_type(db, param.ty)?;
-            inputs.push(Input { name: param.id.name(db.upcast()).into(), ty: param.ty.format(db) });
+            // Snapshot parameters are passed in the calldata as their inner type.
+            let ty = match db.lookup_intern_type(param.ty) {
+                TypeLongId::Snapshot(inner_ty) => inner_ty,
+                _ => param.ty,
+            };
+            self.add_type(db, ty)?;
+            inputs.push(Input {
+                name: param.id.name(db.upcast()).into(),
+                ty: ty.format(db),
+                kind: None,
+            });
         }
         Ok((inputs, state_mutability))
     }
@@ -417,8 +494,11 @@ impl AbiBuilder {
         let signature =
             db.impl_function_signature(impl_function_id).map_err(|_| ABIError::CompilationError)?;
 
-        let (inputs, state_mutability) =
+        let (mut inputs, state_mutability) =
             self.get_function_signature_inputs_and_mutability(&signature, storage_type, db)?;
+        if impl_function_id.has_attr(db, REST_ARGS_ATTR).map_err(|_| ABIError::CompilationError)? {
+            mark_rest_input(&mut inputs);
+        }
 
         let outputs = self.get_signature_outputs(db, &signature)?;
 
@@ -556,6 +636,14 @@ impl AbiBuilder {
     }
 }
 
+/// Marks the trailing input of an entry point annotated with REST_ARGS_ATTR, which is filled with
+/// the remaining calldata instead of being deserialized.
+fn mark_rest_input(inputs: &mut [Input]) {
+    if let Some(input) = inputs.last_mut() {
+        input.kind = Some(InputKind::Rest);
+    }
+}
+
 fn get_type_name(db: &dyn SemanticGroup, ty: TypeId) -> Option<SmolStr> {
     let concrete_ty = try_extract_matches!(db.lookup_intern_type(ty), TypeLongId::Concrete)?;
     Some(concrete_ty.generic_type(db).name(db.upcast()))
@@ -733,6 +821,17 @@ pub struct Input {
     pub name: String,
     #[serde(rename = "type")]
     pub ty: String,
+    /// How the input is encoded in the calldata, if not by the `Serde` impl of its type.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub kind: Option<InputKind>,
+}
+
+/// Function input kind.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum InputKind {
+    /// An `Array::<felt252>` filled with all the remaining calldata, without a length prefix.
+    #[serde(rename = "rest")]
+    Rest,
 }
 
 /// Function Output ABI.
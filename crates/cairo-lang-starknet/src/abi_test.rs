@@ -1,11 +1,20 @@
+use std::sync::Arc;
+
+use cairo_lang_compiler::db::RootDatabase;
 use cairo_lang_defs::ids::ModuleItemId;
+use cairo_lang_filesystem::db::FilesGroup;
 use cairo_lang_semantic::db::SemanticGroup;
-use cairo_lang_semantic::test_utils::{setup_test_module, SemanticDatabaseForTesting};
+use cairo_lang_semantic::test_utils::{
+    setup_test_crate, setup_test_module, SemanticDatabaseForTesting,
+};
 use cairo_lang_utils::extract_matches;
 use indoc::indoc;
+use itertools::Itertools;
 use pretty_assertions::assert_eq;
 
-use crate::abi::AbiBuilder;
+use crate::abi::{AbiBuilder, Item, StateMutability};
+use crate::contract::find_contracts;
+use crate::plugin::StarkNetPlugin;
 
 #[test]
 fn test_abi() {
@@ -177,3 +186,430 @@ fn test_abi() {
           ]"#}
     );
 }
+
+#[test]
+fn test_abi_of_impl_external_functions() {
+    let db = &mut RootDatabase::builder()
+        .detect_corelib()
+        .with_semantic_plugin(Arc::new(StarkNetPlugin::default()))
+        .build()
+        .unwrap();
+    setup_test_crate(
+        db,
+        indoc! {"
+            #[starknet::contract]
+            mod TestContract {
+                #[storage]
+                struct Storage {}
+
+                trait ValueTrait {
+                    fn get_value(self: @ContractState) -> felt252;
+                    fn internal_value(self: @ContractState) -> felt252;
+                }
+
+                impl ValueImpl of ValueTrait {
+                    #[external(v0)]
+                    fn get_value(self: @ContractState) -> felt252 {
+                        1
+                    }
+
+                    fn internal_value(self: @ContractState) -> felt252 {
+                        2
+                    }
+                }
+            }
+        "},
+    );
+
+    let contracts = find_contracts(db, &db.crates());
+    let abi = AbiBuilder::submodule_as_contract_abi(db, contracts[0].submodule_id).unwrap();
+    assert_eq!(
+        abi.items
+            .iter()
+            .filter_map(|item| match item {
+                Item::Function(function) => Some(function.name.as_str()),
+                _ => None,
+            })
+            .collect_vec(),
+        vec!["get_value"]
+    );
+}
+
+#[test]
+fn test_abi_of_storage_getter() {
+    let db = &mut RootDatabase::builder()
+        .detect_corelib()
+        .with_semantic_plugin(Arc::new(StarkNetPlugin::default()))
+        .build()
+        .unwrap();
+    setup_test_crate(
+        db,
+        indoc! {"
+            #[starknet::contract]
+            mod TestContract {
+                #[storage]
+                #[storage_getter]
+                struct Storage {
+                    value: felt252,
+                }
+            }
+        "},
+    );
+
+    let contracts = find_contracts(db, &db.crates());
+    let abi = AbiBuilder::submodule_as_contract_abi(db, contracts[0].submodule_id).unwrap();
+    let functions = abi
+        .items
+        .iter()
+        .filter_map(|item| match item {
+            Item::Function(function) => Some(function),
+            _ => None,
+        })
+        .collect_vec();
+    assert_eq!(functions.len(), 1);
+    assert_eq!(functions[0].name, "get_storage");
+    assert_eq!(functions[0].outputs[0].ty, "core::array::Span::<core::felt252>");
+}
+
+#[test]
+fn test_abi_of_snapshot_params() {
+    let db = &mut RootDatabase::builder()
+        .detect_corelib()
+        .with_semantic_plugin(Arc::new(StarkNetPlugin::default()))
+        .build()
+        .unwrap();
+    setup_test_crate(
+        db,
+        indoc! {"
+            #[starknet::contract]
+            mod TestContract {
+                #[storage]
+                struct Storage {}
+
+                #[external(v0)]
+                fn len(self: @ContractState, values: @Array::<felt252>) -> u32 {
+                    values.len()
+                }
+            }
+        "},
+    );
+
+    let contracts = find_contracts(db, &db.crates());
+    let abi = AbiBuilder::submodule_as_contract_abi(db, contracts[0].submodule_id).unwrap();
+    let function = abi
+        .items
+        .iter()
+        .find_map(|item| match item {
+            Item::Function(function) => Some(function),
+            _ => None,
+        })
+        .unwrap();
+    // Snapshot parameters are passed in the calldata as their inner type.
+    assert_eq!(function.inputs[0].ty, "core::array::Array::<core::felt252>");
+}
+
+#[test]
+fn test_abi_of_returns_optional() {
+    let db = &mut RootDatabase::builder()
+        .detect_corelib()
+        .with_semantic_plugin(Arc::new(StarkNetPlugin::default()))
+        .build()
+        .unwrap();
+    setup_test_crate(
+        db,
+        indoc! {"
+            #[starknet::contract]
+            mod TestContract {
+                #[storage]
+                struct Storage {
+                    owner: felt252,
+                }
+
+                #[external(v0)]
+                #[returns_optional]
+                fn get_owner(self: @ContractState) -> felt252 {
+                    self.owner.read()
+                }
+            }
+        "},
+    );
+
+    let contracts = find_contracts(db, &db.crates());
+    let abi = AbiBuilder::submodule_as_contract_abi(db, contracts[0].submodule_id).unwrap();
+    let function = abi
+        .items
+        .iter()
+        .find_map(|item| match item {
+            Item::Function(function) => Some(function),
+            _ => None,
+        })
+        .unwrap();
+    assert_eq!(function.outputs[0].ty, "core::option::Option::<core::felt252>");
+    // The type of the output is added to the ABI.
+    assert!(abi.items.iter().any(|item| matches!(
+        item,
+        Item::Enum(enm) if enm.name == "core::option::Option::<core::felt252>"
+    )));
+}
+
+#[test]
+fn test_abi_of_view_external() {
+    let db = &mut RootDatabase::builder()
+        .detect_corelib()
+        .with_semantic_plugin(Arc::new(StarkNetPlugin::default()))
+        .build()
+        .unwrap();
+    setup_test_crate(
+        db,
+        indoc! {"
+            #[starknet::contract]
+            mod TestContract {
+                #[storage]
+                struct Storage {
+                    value: felt252,
+                }
+
+                #[external(v0)]
+                fn get_value(self: @ContractState) -> felt252 {
+                    self.value.read()
+                }
+
+                #[external(v0)]
+                #[view_external]
+                fn sync_value(self: @ContractState) -> felt252 {
+                    self.value.read()
+                }
+            }
+        "},
+    );
+
+    let contracts = find_contracts(db, &db.crates());
+    let abi = AbiBuilder::submodule_as_contract_abi(db, contracts[0].submodule_id).unwrap();
+    let state_mutabilities = abi
+        .items
+        .iter()
+        .filter_map(|item| match item {
+            Item::Function(function) => Some((function.name.as_str(), &function.state_mutability)),
+            _ => None,
+        })
+        .collect_vec();
+    assert_eq!(
+        state_mutabilities,
+        vec![("get_value", &StateMutability::View), ("sync_value", &StateMutability::External)]
+    );
+}
+
+#[test]
+fn test_abi_of_injected_execution_info() {
+    let db = &mut RootDatabase::builder()
+        .detect_corelib()
+        .with_semantic_plugin(Arc::new(StarkNetPlugin::default()))
+        .build()
+        .unwrap();
+    setup_test_crate(
+        db,
+        indoc! {"
+            #[starknet::contract]
+            mod TestContract {
+                use box::BoxTrait;
+                use starknet::info::ExecutionInfo;
+
+                #[storage]
+                struct Storage {}
+
+                #[external(v0)]
+                #[inject_execution_info]
+                fn delayed_timestamp(self: @ContractState, info: ExecutionInfo, delay: u64) -> u64 {
+                    info.block_info.unbox().block_timestamp + delay
+                }
+            }
+        "},
+    );
+
+    let contracts = find_contracts(db, &db.crates());
+    let abi = AbiBuilder::submodule_as_contract_abi(db, contracts[0].submodule_id).unwrap();
+    let inputs = abi
+        .items
+        .iter()
+        .filter_map(|item| match item {
+            Item::Function(function) => Some(&function.inputs),
+            _ => None,
+        })
+        .flatten()
+        .map(|input| (input.name.as_str(), input.ty.as_str()))
+        .collect_vec();
+    // The execution info is not part of the calldata.
+    assert_eq!(inputs, vec![("delay", "core::integer::u64")]);
+}
+
+#[test]
+fn test_abi_of_rest_args() {
+    let db = &mut RootDatabase::builder()
+        .detect_corelib()
+        .with_semantic_plugin(Arc::new(StarkNetPlugin::default()))
+        .build()
+        .unwrap();
+    setup_test_crate(
+        db,
+        indoc! {"
+            #[starknet::contract]
+            mod TestContract {
+                #[storage]
+                struct Storage {}
+
+                #[external(v0)]
+                #[rest_args]
+                fn sum(self: @ContractState, first: felt252, rest: Array<felt252>) -> felt252 {
+                    first
+                }
+            }
+        "},
+    );
+
+    let contracts = find_contracts(db, &db.crates());
+    let abi = AbiBuilder::submodule_as_contract_abi(db, contracts[0].submodule_id).unwrap();
+    let function = abi
+        .items
+        .iter()
+        .find_map(|item| match item {
+            Item::Function(function) => Some(function),
+            _ => None,
+        })
+        .unwrap();
+    // The rest input is not prefixed by its length in the calldata, unlike the other arrays.
+    assert_eq!(
+        serde_json::to_string_pretty(&function.inputs).unwrap(),
+        indoc! {r#"
+            [
+              {
+                "name": "first",
+                "type": "core::felt252"
+              },
+              {
+                "name": "rest",
+                "type": "core::array::Array::<core::felt252>",
+                "kind": "rest"
+              }
+            ]"#}
+        .trim_start()
+    );
+}
+
+#[test]
+fn test_abi_matches_starknet_abi_schema() {
+    let db = &mut RootDatabase::builder()
+        .detect_corelib()
+        .with_semantic_plugin(Arc::new(StarkNetPlugin::default()))
+        .build()
+        .unwrap();
+    setup_test_crate(
+        db,
+        indoc! {"
+            #[derive(Copy, Drop, Serde)]
+            struct Point {
+                x: felt252,
+                y: u128,
+            }
+
+            #[derive(Copy, Drop, Serde)]
+            enum Shape {
+                Dot: Point,
+                Segment: (Point, Point),
+            }
+
+            #[starknet::contract]
+            mod TestContract {
+                use super::{Point, Shape};
+
+                #[storage]
+                struct Storage {}
+
+                #[event]
+                #[derive(Drop, starknet::Event)]
+                enum Event {
+                    Drawn: Drawn,
+                }
+
+                #[derive(Drop, starknet::Event)]
+                struct Drawn {
+                    shape: Shape,
+                }
+
+                #[constructor]
+                fn constructor(ref self: ContractState, origin: Point) {}
+
+                #[external(v0)]
+                fn draw(ref self: ContractState, shape: Shape) -> Option<Point> {
+                    Option::None(())
+                }
+
+                #[external(v0)]
+                fn origin(self: @ContractState) -> Point {
+                    Point { x: 0, y: 0 }
+                }
+
+                #[l1_handler]
+                fn on_message(ref self: ContractState, from_address: felt252, point: Point) {}
+            }
+        "},
+    );
+
+    let contracts = find_contracts(db, &db.crates());
+    let abi = AbiBuilder::submodule_as_contract_abi(db, contracts[0].submodule_id).unwrap();
+    let json: serde_json::Value = serde_json::from_str(&abi.json()).unwrap();
+    let items = json.as_array().expect("The ABI must be an array.");
+
+    // The types defined by the ABI, and the types referenced by it.
+    let mut defined_types = vec![];
+    let mut referenced_types = vec![];
+    for item in items {
+        let name = item["name"].as_str().expect("ABI items must have a name.");
+        match item["type"].as_str().expect("ABI items must have a type.") {
+            "function" | "l1_handler" => {
+                referenced_types.extend(schema_fields(&item["inputs"], true));
+                referenced_types.extend(schema_fields(&item["outputs"], false));
+                assert!(
+                    matches!(item["state_mutability"].as_str(), Some("external" | "view")),
+                    "Invalid state mutability of `{name}`."
+                );
+            }
+            "constructor" => referenced_types.extend(schema_fields(&item["inputs"], true)),
+            "struct" => {
+                referenced_types.extend(schema_fields(&item["members"], true));
+                defined_types.push(name.to_string());
+            }
+            "enum" => {
+                referenced_types.extend(schema_fields(&item["variants"], true));
+                defined_types.push(name.to_string());
+            }
+            "event" => {
+                assert!(
+                    matches!(item["kind"].as_str(), Some("struct" | "enum")),
+                    "Invalid kind of event `{name}`."
+                );
+            }
+            ty => panic!("Invalid ABI item type `{ty}`."),
+        }
+    }
+
+    // User types are defined in the ABI, so that clients can encode them.
+    for ty in referenced_types.iter().filter(|ty| ty.starts_with("test::")) {
+        assert!(defined_types.contains(ty), "Type `{ty}` is not defined in the ABI.");
+    }
+    assert!(defined_types.contains(&"test::Point".to_string()));
+    assert!(defined_types.contains(&"test::Shape".to_string()));
+}
+
+/// Validates the fields of an ABI item against the Starknet ABI schema, and returns their types.
+/// Fields have a `name` iff `named` is set.
+fn schema_fields(fields: &serde_json::Value, named: bool) -> Vec<String> {
+    fields
+        .as_array()
+        .expect("ABI fields must be an array.")
+        .iter()
+        .map(|field| {
+            assert_eq!(field.get("name").map(|name| name.is_string()), named.then_some(true));
+            field["type"].as_str().expect("ABI fields must have a type.").to_string()
+        })
+        .collect()
+}
@@ -12,13 +12,17 @@ use cairo_lang_semantic::Expr;
 use cairo_lang_sierra::ids::FunctionId;
 use cairo_lang_sierra_generator::db::SierraGenGroup;
 use cairo_lang_sierra_generator::replace_ids::SierraIdReplacer;
+use cairo_lang_syntax::attribute::structured::{Attribute, AttributeArg, AttributeArgVariant};
+use cairo_lang_syntax::node::ast;
+use cairo_lang_syntax::node::db::SyntaxGroup;
 use cairo_lang_utils::ordered_hash_map::OrderedHashMap;
 use cairo_lang_utils::{extract_matches, try_extract_matches};
 use num_bigint::BigUint;
 use sha3::{Digest, Keccak256};
+use smol_str::SmolStr;
 
 use crate::contract_class::{extract_semantic_entrypoints, SemanticEntryPoints};
-use crate::plugin::aux_data::StarkNetContractAuxData;
+use crate::plugin::aux_data::{StarkNetContractAuxData, StorageMemberLayout};
 
 #[cfg(test)]
 #[path = "contract_test.rs"]
@@ -28,12 +32,25 @@ mod test;
 pub struct ContractDeclaration {
     /// The id of the module that defines the contract.
     pub submodule_id: SubmoduleId,
+    /// The name of the generated module containing the external functions.
+    pub external_module_name: SmolStr,
+    /// The strings hashed into the selectors of the entry points annotated with
+    /// SELECTOR_NAME_ATTR, by entry point name.
+    pub selector_names: OrderedHashMap<SmolStr, SmolStr>,
+    /// The storage layout of the contract, by storage member in declaration order.
+    pub storage_layout: Vec<StorageMemberLayout>,
 }
 
 impl ContractDeclaration {
     pub fn module_id(&self) -> ModuleId {
         ModuleId::Submodule(self.submodule_id)
     }
+
+    /// Returns the selector of the entry point `name`.
+    pub fn entry_point_selector(&self, name: &str) -> BigUint {
+        let selector_name = self.selector_names.get(name).map_or(name, |name| name.as_str());
+        starknet_keccak(selector_name.as_bytes())
+    }
 }
 
 /// A variant of eth-keccak that computes a value that fits in a Starknet field element.
@@ -47,6 +64,26 @@ pub fn starknet_keccak(data: &[u8]) -> BigUint {
     BigUint::from_bytes_be(&result)
 }
 
+/// Returns the string given by a SELECTOR_NAME_ATTR attribute, if its argument is a single
+/// non-empty short string literal of printable characters.
+pub fn selector_name_from_attr(db: &dyn SyntaxGroup, attr: &Attribute) -> Option<SmolStr> {
+    let [
+        AttributeArg {
+            variant: AttributeArgVariant::Unnamed { value: ast::Expr::ShortString(value), .. },
+            ..
+        },
+    ] = &attr.args[..] else {
+        return None;
+    };
+    if value.suffix(db).is_some() {
+        return None;
+    }
+    value
+        .string_value(db)
+        .filter(|name| !name.is_empty() && name.bytes().all(|byte| byte.is_ascii_graphic()))
+        .map(SmolStr::from)
+}
+
 /// Finds the inline modules annotated as contracts in the given crate_ids and
 /// returns the corresponding ContractDeclarations.
 pub fn find_contracts(db: &dyn SemanticGroup, crate_ids: &[CrateId]) -> Vec<ContractDeclaration> {
@@ -80,7 +117,12 @@ pub fn find_contracts(db: &dyn SemanticGroup, crate_ids: &[CrateId]) -> Vec<Cont
 
                 for contract_name in &aux_data.contracts {
                     if let ModuleId::Submodule(submodule_id) = *module_id {
-                        contracts.push(ContractDeclaration { submodule_id });
+                        contracts.push(ContractDeclaration {
+                            submodule_id,
+                            external_module_name: aux_data.external_module_name.clone(),
+                            selector_names: aux_data.selector_names.clone(),
+                            storage_layout: aux_data.storage_layout.clone(),
+                        });
                     } else {
                         panic!("Contract `{contract_name}` was not found.");
                     }
@@ -174,13 +216,17 @@ fn analyze_contract<T: SierraIdReplacer>(
     // Extract functions.
     let SemanticEntryPoints { external, l1_handler, constructor } =
         extract_semantic_entrypoints(db, contract)?;
-    let externals =
-        external.into_iter().map(|f| get_selector_and_sierra_function(db, f, replacer)).collect();
-    let l1_handlers =
-        l1_handler.into_iter().map(|f| get_selector_and_sierra_function(db, f, replacer)).collect();
+    let externals = external
+        .into_iter()
+        .map(|f| get_selector_and_sierra_function(db, contract, f, replacer))
+        .collect();
+    let l1_handlers = l1_handler
+        .into_iter()
+        .map(|f| get_selector_and_sierra_function(db, contract, f, replacer))
+        .collect();
     let constructors: Vec<_> = constructor
         .into_iter()
-        .map(|f| get_selector_and_sierra_function(db, f, replacer))
+        .map(|f| get_selector_and_sierra_function(db, contract, f, replacer))
         .collect();
     assert!(constructors.len() <= 1, "Expected at most one constructor.");
 
@@ -196,6 +242,7 @@ fn analyze_contract<T: SierraIdReplacer>(
 /// Returns the selector and the sierra function id.
 pub fn get_selector_and_sierra_function<T: SierraIdReplacer>(
     db: &dyn SierraGenGroup,
+    contract: &ContractDeclaration,
     function_with_body: ConcreteFunctionWithBodyId,
     replacer: &T,
 ) -> (Felt252, FunctionId) {
@@ -209,6 +256,6 @@ pub fn get_selector_and_sierra_function<T: SierraIdReplacer>(
     )
     .expect("Entrypoint cannot be a generated function.");
     let selector =
-        Felt252::try_from(starknet_keccak(semantic.name(db.upcast()).as_bytes())).unwrap();
+        Felt252::try_from(contract.entry_point_selector(&semantic.name(db.upcast()))).unwrap();
     (selector, sierra_id)
 }
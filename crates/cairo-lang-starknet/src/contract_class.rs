@@ -27,7 +27,7 @@ use crate::contract::{
     find_contracts, get_module_functions, get_selector_and_sierra_function, ContractDeclaration,
 };
 use crate::felt252_serde::sierra_to_felt252s;
-use crate::plugin::consts::{CONSTRUCTOR_MODULE, EXTERNAL_MODULE, L1_HANDLER_MODULE};
+use crate::plugin::consts::{CONSTRUCTOR_MODULE, L1_HANDLER_MODULE};
 use crate::plugin::StarkNetPlugin;
 
 #[cfg(test)]
@@ -180,10 +180,10 @@ fn compile_contract_with_prepared_and_checked_db(
     let sierra_program = replacer.apply(&sierra_program);
 
     let entry_points_by_type = ContractEntryPoints {
-        external: get_entry_points(db, &external, &replacer)?,
-        l1_handler: get_entry_points(db, &l1_handler, &replacer)?,
+        external: get_entry_points(db, contract, &external, &replacer)?,
+        l1_handler: get_entry_points(db, contract, &l1_handler, &replacer)?,
         // Later generation of ABI verifies that there is up to one constructor.
-        constructor: get_entry_points(db, &constructor, &replacer)?,
+        constructor: get_entry_points(db, contract, &constructor, &replacer)?,
     };
     let contract_class = ContractClass {
         sierra_program: sierra_to_felt252s(
@@ -215,7 +215,7 @@ pub fn extract_semantic_entrypoints(
     db: &dyn SierraGenGroup,
     contract: &ContractDeclaration,
 ) -> core::result::Result<SemanticEntryPoints, anyhow::Error> {
-    let external: Vec<_> = get_module_functions(db.upcast(), contract, EXTERNAL_MODULE)?
+    let external: Vec<_> = get_module_functions(db.upcast(), contract, &contract.external_module_name)?
         .into_iter()
         .flat_map(|f| ConcreteFunctionWithBodyId::from_no_generics_free(db.upcast(), f))
         .collect();
@@ -236,13 +236,14 @@ pub fn extract_semantic_entrypoints(
 /// Returns the entry points given their IDs sorted by selectors.
 fn get_entry_points(
     db: &RootDatabase,
+    contract: &ContractDeclaration,
     entry_point_functions: &[ConcreteFunctionWithBodyId],
     replacer: &CanonicalReplacer,
 ) -> Result<Vec<ContractEntryPoint>> {
     let mut entry_points = vec![];
     for function_with_body_id in entry_point_functions {
         let (selector, sierra_id) =
-            get_selector_and_sierra_function(db, *function_with_body_id, replacer);
+            get_selector_and_sierra_function(db, contract, *function_with_body_id, replacer);
 
         entry_points.push(ContractEntryPoint {
             selector: selector.to_biguint(),
@@ -6,6 +6,7 @@ use cairo_lang_semantic::test_utils::{get_crate_semantic_diagnostics, setup_test
 use indoc::indoc;
 use itertools::Itertools;
 use pretty_assertions::assert_eq;
+use test_case::test_case;
 
 use crate::contract::{find_contracts, get_module_functions, starknet_keccak};
 use crate::plugin::consts::EXTERNAL_MODULE;
@@ -58,9 +59,159 @@ fn test_contract_resolving() {
 }
 
 #[test]
-fn test_starknet_keccak() {
+fn test_contract_resolving_with_external_module_name() {
+    let db = &mut RootDatabase::builder()
+        .detect_corelib()
+        .with_semantic_plugin(Arc::new(StarkNetPlugin::default()))
+        .build()
+        .unwrap();
+    let crate_id = setup_test_crate(
+        db,
+        indoc! {"
+            #[starknet::contract]
+            #[external_module(custom_external)]
+            mod ERC20 {
+                #[storage]
+                struct Storage {}
+
+                #[external(v0)]
+                fn ep1(ref self: ContractState) {}
+            }
+        "},
+    );
+
+    let contracts = find_contracts(db, &db.crates());
+    assert_eq!(contracts.len(), 1);
+    assert_eq!(contracts[0].external_module_name, "custom_external");
+
     assert_eq!(
-        format!("0x{:x}", starknet_keccak("__execute__".as_bytes())),
-        "0x15d40a3d6ca2ac30f4031e42be28da9b056fef9bb7357ac5e85627ee876e5ad",
-    )
+        get_module_functions(db, &contracts[0], &contracts[0].external_module_name)
+            .unwrap()
+            .into_iter()
+            .map(|func_id| func_id.name(db))
+            .collect_vec(),
+        vec!["ep1"]
+    );
+    assert!(get_module_functions(db, &contracts[0], EXTERNAL_MODULE).is_err());
+
+    // Assert no semantic diagnostics
+    get_crate_semantic_diagnostics(db, crate_id)
+        .expect_with_db(db, "Unexpected semantic diagnostics");
+}
+
+#[test]
+fn test_contract_selector_names() {
+    let db = &mut RootDatabase::builder()
+        .detect_corelib()
+        .with_semantic_plugin(Arc::new(StarkNetPlugin::default()))
+        .build()
+        .unwrap();
+    let crate_id = setup_test_crate(
+        db,
+        indoc! {"
+            #[starknet::contract]
+            mod ERC20 {
+                #[storage]
+                struct Storage {}
+
+                #[external(v0)]
+                #[selector_name('balanceOf')]
+                fn balance_of(self: @ContractState) -> felt252 {
+                    0
+                }
+
+                #[external(v0)]
+                fn transfer(ref self: ContractState) {}
+            }
+        "},
+    );
+
+    let contracts = find_contracts(db, &db.crates());
+    assert_eq!(contracts.len(), 1);
+    assert_eq!(
+        format!("0x{:x}", contracts[0].entry_point_selector("balance_of")),
+        "0x2e4263afad30923c891518314c3c95dbe830a16874e8abc5777a9a20b54c76e"
+    );
+    assert_eq!(
+        format!("0x{:x}", contracts[0].entry_point_selector("transfer")),
+        "0x83afd3f4caedc6eebf44246fe54e38c95e3179a5ec9ea81740eca5b482d12e"
+    );
+
+    // Assert no semantic diagnostics
+    get_crate_semantic_diagnostics(db, crate_id)
+        .expect_with_db(db, "Unexpected semantic diagnostics");
+}
+
+#[test]
+fn test_contract_storage_layout() {
+    let db = &mut RootDatabase::builder()
+        .detect_corelib()
+        .with_semantic_plugin(Arc::new(StarkNetPlugin::default()))
+        .build()
+        .unwrap();
+    let crate_id = setup_test_crate(
+        db,
+        indoc! {"
+            #[derive(Copy, Drop, storage_access::StorageAccess)]
+            struct Point {
+                x: felt252,
+                y: felt252,
+            }
+
+            #[starknet::contract]
+            mod TestContract {
+                use super::Point;
+
+                #[storage]
+                struct Storage {
+                    value: felt252,
+                    #[storage_slot(0x10)]
+                    supply: u256,
+                    balances: LegacyMap::<felt252, u128>,
+                    origin: Point,
+                }
+            }
+        "},
+    );
+
+    let contracts = find_contracts(db, &db.crates());
+    let layout = contracts[0]
+        .storage_layout
+        .iter()
+        .map(|member| {
+            (member.name.as_str(), member.ty.as_str(), member.base_address.clone(), member.slots)
+        })
+        .collect_vec();
+    assert_eq!(
+        layout,
+        vec![
+            ("value", "felt252", starknet_keccak(b"value"), Some(1)),
+            ("supply", "u256", 0x10_u32.into(), Some(2)),
+            ("balances", "LegacyMap::<felt252, u128>", starknet_keccak(b"balances"), None),
+            // The size of user defined types isn't known syntactically.
+            ("origin", "Point", starknet_keccak(b"origin"), Some(256)),
+        ]
+    );
+
+    // Assert no semantic diagnostics
+    get_crate_semantic_diagnostics(db, crate_id)
+        .expect_with_db(db, "Unexpected semantic diagnostics");
+}
+
+#[test_case("__execute__", "0x15d40a3d6ca2ac30f4031e42be28da9b056fef9bb7357ac5e85627ee876e5ad")]
+#[test_case("__validate__", "0x162da33a4585851fe8d3af3c2a9c60b557814e221e0d4f30ff0b2189d9c7775")]
+#[test_case(
+    "__validate_declare__",
+    "0x289da278a8dc833409cabfdad1581e8e7d40e42dcaed693fa4008dcdb4963b3"
+)]
+#[test_case("constructor", "0x28ffe4ff0f226a9107253e17a904099aa4f63a02a5621de0576e5aa71bc5194")]
+#[test_case("transfer", "0x83afd3f4caedc6eebf44246fe54e38c95e3179a5ec9ea81740eca5b482d12e")]
+#[test_case("balanceOf", "0x2e4263afad30923c891518314c3c95dbe830a16874e8abc5777a9a20b54c76e")]
+#[test_case("approve", "0x219209e083275171774dab1df80982e9df2096516f06319c5c6d71ae0a8480c")]
+#[test_case("Transfer", "0x99cd8bde557814842a3121e8ddfd433a539b8c9f14bf31ebf108d12e6196e9")]
+fn test_starknet_keccak(name: &str, expected_selector: &str) {
+    let selector = starknet_keccak(name.as_bytes());
+    // Selectors are masked to 250 bits.
+    assert!(selector.bits() <= 250);
+    assert_eq!(format!("0x{selector:x}"), expected_selector);
 }
@@ -1,12 +1,31 @@
+use std::iter::successors;
+
+use cairo_lang_defs::ids::{TopLevelLanguageElementId, TraitId};
 use cairo_lang_defs::plugin::GeneratedFileAuxData;
 use cairo_lang_diagnostics::DiagnosticEntry;
+use cairo_lang_semantic::corelib::core_crate;
 use cairo_lang_semantic::db::SemanticGroup;
+use cairo_lang_semantic::diagnostic::SemanticDiagnosticKind;
+use cairo_lang_semantic::expr::inference::InferenceError;
+use cairo_lang_semantic::items::enm::SemanticEnumEx;
+use cairo_lang_semantic::items::imp::{get_impl_at_context, ImplLookupContext};
+use cairo_lang_semantic::items::trt::{ConcreteTraitId, ConcreteTraitLongId};
 use cairo_lang_semantic::patcher::Patches;
 use cairo_lang_semantic::plugin::{
     AsDynGeneratedFileAuxData, PluginAuxData, PluginMappedDiagnostic,
 };
-use cairo_lang_semantic::SemanticDiagnostic;
+use cairo_lang_semantic::{
+    ConcreteTypeId, GenericArgumentId, SemanticDiagnostic, TypeId, TypeLongId,
+};
+use cairo_lang_syntax::node::ids::SyntaxStablePtrId;
+use cairo_lang_syntax::node::helpers::GetIdentifier;
+use cairo_lang_syntax::node::kind::SyntaxKind;
+use cairo_lang_syntax::node::{ast, SyntaxNode, TypedSyntaxNode};
+use cairo_lang_utils::ordered_hash_map::OrderedHashMap;
+use itertools::Itertools;
+use num_bigint::BigUint;
 
+use super::consts::WRAPPER_ARG_PREFIX;
 use super::events::EventData;
 
 /// Contract related auxiliary data of the Starknet plugin.
@@ -17,7 +36,35 @@ pub struct StarkNetContractAuxData {
 
     /// A list of contracts that were processed by the plugin.
     pub contracts: Vec<smol_str::SmolStr>,
+
+    /// The name of the generated module containing the external functions.
+    pub external_module_name: smol_str::SmolStr,
+
+    /// The strings hashed into the selectors of the entry points annotated with
+    /// SELECTOR_NAME_ATTR, by entry point name.
+    pub selector_names: OrderedHashMap<smol_str::SmolStr, smol_str::SmolStr>,
+
+    /// The storage layout of the contract, by storage member in declaration order.
+    pub storage_layout: Vec<StorageMemberLayout>,
 }
+
+/// The storage layout of a member of the storage struct of a contract.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct StorageMemberLayout {
+    /// The name of the member.
+    pub name: smol_str::SmolStr,
+    /// The type of the member, as written in the storage struct.
+    pub ty: String,
+    /// The address of the first storage slot of the member. For mappings, the address hashed with
+    /// the keys into the addresses of the entries, and for members of FLAT_ATTR struct types, the
+    /// address hashed with the names of the fields.
+    pub base_address: BigUint,
+    /// The number of consecutive storage slots from the base address occupied by the member, or
+    /// None for mappings and members of FLAT_ATTR struct types. Types whose size isn't known
+    /// syntactically are counted as occupying MAX_STORAGE_MEMBER_SLOTS slots.
+    pub slots: Option<usize>,
+}
+
 impl GeneratedFileAuxData for StarkNetContractAuxData {
     fn as_any(&self) -> &dyn std::any::Any {
         self
@@ -37,7 +84,29 @@ impl PluginAuxData for StarkNetContractAuxData {
         db: &(dyn SemanticGroup + 'static),
         diag: &dyn std::any::Any,
     ) -> Option<PluginMappedDiagnostic> {
-        let Some(diag) = diag.downcast_ref::<SemanticDiagnostic>() else {return None;};
+        let diag = diag.downcast_ref::<SemanticDiagnostic>()?;
+        if is_missing_serde_impl(db, diag) {
+            let requirement = if is_ref_arg_serde(db, diag) {
+                format!(
+                    "`ref` parameters are deserialized from the calldata and serialized back \
+                     into the output of the entry point, so their types must implement both \
+                     directions of `{SERDE_TRAIT_PATH}`"
+                )
+            } else {
+                format!(
+                    "Entry point parameters and return types must implement `{SERDE_TRAIT_PATH}`"
+                )
+            };
+            return map_missing_serde_diag(
+                db,
+                &self.patches,
+                diag,
+                &format!(
+                    "{requirement}, e.g. using `#[derive(Serde)]`. Supported corelib types: {}.",
+                    corelib_serde_types(db).join(", ")
+                ),
+            );
+        }
         let span = self
             .patches
             .translate(db.upcast(), diag.stable_location.diagnostic_location(db.upcast()).span)?;
@@ -45,6 +114,283 @@ impl PluginAuxData for StarkNetContractAuxData {
     }
 }
 
+/// The full path of the trait used to serialize the entry points' and dispatchers' parameters and
+/// return types, and the event fields.
+const SERDE_TRAIT_PATH: &str = "core::serde::Serde";
+
+/// Returns true if the diagnostic is about a missing `Serde` implementation.
+fn is_missing_serde_impl(db: &dyn SemanticGroup, diag: &SemanticDiagnostic) -> bool {
+    matches!(
+        &diag.kind,
+        SemanticDiagnosticKind::InternalInferenceError(InferenceError::NoImplsFound {
+            concrete_trait_id
+        }) if concrete_trait_id.trait_id(db).full_path(db.upcast()) == SERDE_TRAIT_PATH
+    )
+}
+
+/// Maps a missing `Serde` implementation diagnostic to the user's type. The diagnostic is reported
+/// on a generated `Serde::<T>::serialize`/`deserialize` call, so it is mapped to `T`, and `hint` is
+/// appended to its message. For nested types, e.g. `(Option::<NotSerializable>, u128)`, it is
+/// reported at the innermost type missing an implementation.
+fn map_missing_serde_diag(
+    db: &(dyn SemanticGroup + 'static),
+    patches: &Patches,
+    diag: &SemanticDiagnostic,
+    hint: &str,
+) -> Option<PluginMappedDiagnostic> {
+    let SemanticDiagnosticKind::InternalInferenceError(InferenceError::NoImplsFound {
+        concrete_trait_id,
+    }) = &diag.kind
+    else {
+        return None;
+    };
+    let [GenericArgumentId::Type(ty)] = concrete_trait_id.generic_args(db)[..] else {
+        return None;
+    };
+    let lookup_context = ImplLookupContext::new(diag.stable_location.module_file_id.0, vec![]);
+    let type_ast = serde_type_arg(db, diag)?;
+    let (type_ast, concrete_trait_id) = innermost_missing_serde_type(
+        db,
+        &lookup_context,
+        diag.stable_location.stable_ptr,
+        concrete_trait_id.trait_id(db),
+        &type_ast,
+        ty,
+    )
+    .unwrap_or((type_ast, *concrete_trait_id));
+    let span = patches
+        .translate(db.upcast(), type_ast.as_syntax_node().span_without_trivia(db.upcast()))?;
+    if is_dict_serde_trait(db, concrete_trait_id) {
+        return Some(PluginMappedDiagnostic {
+            span,
+            message: "Dictionaries cannot be serialized across the ABI. Consider serializing \
+                      their entries instead, e.g. as an `Array` of key and value pairs."
+                .to_string(),
+        });
+    }
+    let mut message = InferenceError::NoImplsFound { concrete_trait_id }.format(db);
+    if let Some(variant) = first_unserializable_variant(
+        db,
+        &lookup_context,
+        diag.stable_location.stable_ptr,
+        concrete_trait_id,
+    ) {
+        message
+            .push_str(&format!(". The payload of the variant `{variant}` does not implement it"));
+    }
+    Some(PluginMappedDiagnostic { span, message: format!("{message}. {hint}") })
+}
+
+/// Returns the syntax node the diagnostic is reported on.
+fn diag_syntax_node(db: &dyn SemanticGroup, diag: &SemanticDiagnostic) -> Option<SyntaxNode> {
+    let file_id = db.module_file(diag.stable_location.module_file_id).ok()?;
+    Some(
+        db.file_syntax(file_id)
+            .ok()?
+            .as_syntax_node()
+            .lookup_ptr(db.upcast(), diag.stable_location.stable_ptr),
+    )
+}
+
+/// Returns true if the diagnostic is reported on the deserialization of a `ref` argument of an
+/// entry point wrapper, i.e. `let mut __arg_a = serde::Serde::<T>::deserialize(ref data)`, or on
+/// its serialization into the output of the wrapper, i.e.
+/// `serde::Serde::<T>::serialize(@__arg_a, ref arr)`. Only `ref` arguments are mutable.
+fn is_ref_arg_serde(db: &dyn SemanticGroup, diag: &SemanticDiagnostic) -> bool {
+    let syntax_db = db.upcast();
+    let Some(node) = diag_syntax_node(db, diag) else {
+        return false;
+    };
+    for node in successors(Some(node), |node| node.parent()) {
+        match node.kind(syntax_db) {
+            SyntaxKind::StatementLet => {
+                let statement = ast::StatementLet::from_syntax_node(syntax_db, node);
+                return statement
+                    .pattern(syntax_db)
+                    .as_syntax_node()
+                    .get_text_without_trivia(syntax_db)
+                    .starts_with(&format!("mut {WRAPPER_ARG_PREFIX}"));
+            }
+            SyntaxKind::ExprFunctionCall => {
+                let call = ast::ExprFunctionCall::from_syntax_node(syntax_db, node);
+                if call.path(syntax_db).identifier(syntax_db) != "serialize" {
+                    continue;
+                }
+                let args = call
+                    .arguments(syntax_db)
+                    .args(syntax_db)
+                    .elements(syntax_db)
+                    .iter()
+                    .map(|arg| arg.as_syntax_node().get_text_without_trivia(syntax_db))
+                    .collect_vec();
+                return matches!(
+                    &args[..],
+                    [value, output] if value.starts_with(&format!("@{WRAPPER_ARG_PREFIX}"))
+                        && output == "ref arr"
+                );
+            }
+            _ => {}
+        }
+    }
+    false
+}
+
+/// Returns the type argument of the `Serde::<T>::...` path the diagnostic is reported on.
+fn serde_type_arg(db: &dyn SemanticGroup, diag: &SemanticDiagnostic) -> Option<ast::Expr> {
+    let syntax_db = db.upcast();
+    let node = diag_syntax_node(db, diag)?;
+    let path_node = successors(Some(node), |node| node.parent())
+        .find(|node| node.kind(syntax_db) == SyntaxKind::ExprPath)?;
+    let type_arg = ast::ExprPath::from_syntax_node(syntax_db, path_node)
+        .elements(syntax_db)
+        .into_iter()
+        .find_map(|segment| match segment {
+            ast::PathSegment::WithGenericArgs(segment) => {
+                segment.generic_args(syntax_db).generic_args(syntax_db).elements(syntax_db).pop()
+            }
+            ast::PathSegment::Simple(_) => None,
+        })?;
+    match type_arg {
+        ast::GenericArg::Expr(type_arg) => Some(type_arg.value(syntax_db)),
+        ast::GenericArg::Underscore(_) => None,
+    }
+}
+
+/// Returns the innermost component of the type `ty`, written as `type_ast`, that has no
+/// implementation of the `Serde` trait `serde_trait_id`, along with the missing concrete trait.
+/// The components of a type are the members of a tuple and the generic arguments of a generic
+/// type, e.g. `T` of `Array::<T>`. Returns None if all the components of `ty` implement `Serde`.
+fn innermost_missing_serde_type(
+    db: &dyn SemanticGroup,
+    lookup_context: &ImplLookupContext,
+    stable_ptr: SyntaxStablePtrId,
+    serde_trait_id: TraitId,
+    type_ast: &ast::Expr,
+    ty: TypeId,
+) -> Option<(ast::Expr, ConcreteTraitId)> {
+    let syntax_db = db.upcast();
+    let components: Vec<(ast::Expr, TypeId)> = match (db.lookup_intern_type(ty), type_ast) {
+        (TypeLongId::Tuple(member_types), ast::Expr::Tuple(tuple_ast)) => {
+            let member_asts = tuple_ast.expressions(syntax_db).elements(syntax_db);
+            member_asts.into_iter().zip(member_types).collect()
+        }
+        (TypeLongId::Concrete(concrete_type), ast::Expr::Path(path_ast)) => {
+            let Some(ast::PathSegment::WithGenericArgs(segment)) =
+                path_ast.elements(syntax_db).pop()
+            else {
+                return None;
+            };
+            segment
+                .generic_args(syntax_db)
+                .generic_args(syntax_db)
+                .elements(syntax_db)
+                .into_iter()
+                .zip(concrete_type.generic_args(db))
+                .filter_map(|(arg_ast, arg)| match (arg_ast, arg) {
+                    (ast::GenericArg::Expr(arg_ast), GenericArgumentId::Type(arg_ty)) => {
+                        Some((arg_ast.value(syntax_db), arg_ty))
+                    }
+                    _ => None,
+                })
+                .collect()
+        }
+        _ => return None,
+    };
+    components.into_iter().find_map(|(component_ast, component_ty)| {
+        let concrete_trait_id =
+            missing_serde_trait(db, lookup_context, stable_ptr, serde_trait_id, component_ty)?;
+        Some(
+            innermost_missing_serde_type(
+                db,
+                lookup_context,
+                stable_ptr,
+                serde_trait_id,
+                &component_ast,
+                component_ty,
+            )
+            .unwrap_or((component_ast, concrete_trait_id)),
+        )
+    })
+}
+
+/// The full paths of the corelib dictionary types.
+const DICT_TYPE_PATHS: [&str; 2] = ["core::dict::Felt252Dict", "core::dict::SquashedFelt252Dict"];
+
+/// Returns true if the type of the concrete `Serde` trait is a dictionary type.
+fn is_dict_serde_trait(db: &dyn SemanticGroup, concrete_trait_id: ConcreteTraitId) -> bool {
+    let [GenericArgumentId::Type(ty)] = concrete_trait_id.generic_args(db)[..] else {
+        return false;
+    };
+    let TypeLongId::Concrete(concrete_type) = db.lookup_intern_type(ty) else {
+        return false;
+    };
+    DICT_TYPE_PATHS.contains(&concrete_type.generic_type(db).format(db.upcast()).as_str())
+}
+
+/// Returns the concrete `Serde` trait `serde_trait_id` of `ty` if it has no implementation.
+fn missing_serde_trait(
+    db: &dyn SemanticGroup,
+    lookup_context: &ImplLookupContext,
+    stable_ptr: SyntaxStablePtrId,
+    serde_trait_id: TraitId,
+    ty: TypeId,
+) -> Option<ConcreteTraitId> {
+    let concrete_trait_id = db.intern_concrete_trait(ConcreteTraitLongId {
+        trait_id: serde_trait_id,
+        generic_args: vec![GenericArgumentId::Type(ty)],
+    });
+    match get_impl_at_context(db, lookup_context.clone(), concrete_trait_id, stable_ptr) {
+        Ok(_) => None,
+        Err(_) => Some(concrete_trait_id),
+    }
+}
+
+/// Returns the name of the first variant whose payload has no `Serde` implementation, if the type
+/// of the missing concrete `Serde` trait is an enum. Deriving `Serde` for such an enum would fail.
+fn first_unserializable_variant(
+    db: &dyn SemanticGroup,
+    lookup_context: &ImplLookupContext,
+    stable_ptr: SyntaxStablePtrId,
+    concrete_trait_id: ConcreteTraitId,
+) -> Option<String> {
+    let [GenericArgumentId::Type(ty)] = concrete_trait_id.generic_args(db)[..] else {
+        return None;
+    };
+    let TypeLongId::Concrete(ConcreteTypeId::Enum(concrete_enum_id)) = db.lookup_intern_type(ty)
+    else {
+        return None;
+    };
+    let serde_trait_id = concrete_trait_id.trait_id(db);
+    let variant = db.concrete_enum_variants(concrete_enum_id).ok()?.into_iter().find(|variant| {
+        missing_serde_trait(db, lookup_context, stable_ptr, serde_trait_id, variant.ty).is_some()
+    })?;
+    Some(format!(
+        "{}::{}",
+        concrete_enum_id.enum_id(db).name(db.upcast()),
+        variant.id.name(db.upcast())
+    ))
+}
+
+/// Returns the types that implement `Serde` in the corelib.
+/// Computed from the corelib itself, so it stays in sync as more implementations are added.
+fn corelib_serde_types(db: &dyn SemanticGroup) -> Vec<String> {
+    db.crate_modules(core_crate(db))
+        .iter()
+        .flat_map(|module_id| db.module_impls_ids(*module_id).unwrap_or_default())
+        .filter_map(|impl_def_id| {
+            let concrete_trait_id = db.impl_def_concrete_trait(impl_def_id).ok()?;
+            if concrete_trait_id.trait_id(db).full_path(db.upcast()) != SERDE_TRAIT_PATH {
+                return None;
+            }
+            match concrete_trait_id.generic_args(db).first()? {
+                GenericArgumentId::Type(ty) => Some(ty.format(db)),
+                _ => None,
+            }
+        })
+        .unique()
+        .collect()
+}
+
 /// Contract related auxiliary data of the Starknet plugin.
 #[derive(Debug, PartialEq, Eq)]
 pub struct StarkNetABIAuxData {
@@ -71,6 +417,17 @@ impl PluginAuxData for StarkNetABIAuxData {
         diag: &dyn std::any::Any,
     ) -> Option<PluginMappedDiagnostic> {
         let Some(diag) = diag.downcast_ref::<SemanticDiagnostic>() else {return None;};
+        if is_missing_serde_impl(db, diag) {
+            return map_missing_serde_diag(
+                db,
+                &self.patches,
+                diag,
+                &format!(
+                    "Interface function parameters and return types must implement \
+                     `{SERDE_TRAIT_PATH}`, e.g. using `#[derive(Serde)]`."
+                ),
+            );
+        }
         let span = self
             .patches
             .translate(db.upcast(), diag.stable_location.diagnostic_location(db.upcast()).span)?;
@@ -105,6 +462,17 @@ impl PluginAuxData for StarkNetEventAuxData {
         diag: &dyn std::any::Any,
     ) -> Option<PluginMappedDiagnostic> {
         let Some(diag) = diag.downcast_ref::<SemanticDiagnostic>() else { return None; };
+        if is_missing_serde_impl(db, diag) {
+            return map_missing_serde_diag(
+                db,
+                &self.patches,
+                diag,
+                &format!(
+                    "Event fields must implement `{SERDE_TRAIT_PATH}`, e.g. using \
+                     `#[derive(Serde)]`."
+                ),
+            );
+        }
         let span = self
             .patches
             .translate(db.upcast(), diag.stable_location.diagnostic_location(db.upcast()).span)?;
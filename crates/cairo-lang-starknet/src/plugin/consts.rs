@@ -1,7 +1,37 @@
 pub const ABI_TRAIT: &str = "__abi";
+pub const ABI_IMPL: &str = "__abi_impl";
+pub const IS_VIEW_FUNCTION: &str = "__is_view";
+pub const ENTRY_POINTS_FUNCTION: &str = "__entry_points";
+pub const SELECTOR_NAMES_FUNCTION: &str = "__selector_names";
+pub const DEPLOY_FUNCTION: &str = "deploy";
+pub const STORAGE_GETTER_FUNCTION: &str = "get_storage";
+pub const SUPPORTS_INTERFACE_FUNCTION: &str = "supports_interface";
+pub const UPGRADE_FUNCTION: &str = "upgrade";
+pub const CONTRACT_NAME_CONST: &str = "__CONTRACT_NAME";
+pub const STORAGE_RESET_FUNCTION: &str = "__reset_storage";
+pub const STORAGE_MIGRATE_FUNCTION: &str = "__migrate";
 pub const EXTERNAL_MODULE: &str = "__external";
+pub const RESULT_EXTERNAL_MODULE: &str = "__external_result";
+pub const RECORDING_EXTERNAL_MODULE: &str = "__external_recording";
+pub const SELECTORS_MODULE: &str = "__selectors";
+pub const CALLDATA_MODULE: &str = "__calldata";
 pub const L1_HANDLER_MODULE: &str = "__l1_handler";
 pub const CONSTRUCTOR_MODULE: &str = "__constructor";
+pub const STORAGE_MODULE: &str = "__storage";
+/// The names of the modules, traits and impls generated in every contract module, which the
+/// external module can't be renamed to by EXTERNAL_MODULE_ATTR. Every module added to the generated
+/// code must be listed here.
+pub(super) const GENERATED_MODULE_NAMES: [&str; 9] = [
+    ABI_TRAIT,
+    ABI_IMPL,
+    RESULT_EXTERNAL_MODULE,
+    RECORDING_EXTERNAL_MODULE,
+    SELECTORS_MODULE,
+    CALLDATA_MODULE,
+    L1_HANDLER_MODULE,
+    CONSTRUCTOR_MODULE,
+    STORAGE_MODULE,
+];
 pub const STORAGE_STRUCT_NAME: &str = "Storage";
 pub const EVENT_TYPE_NAME: &str = "Event";
 pub const CONTRACT_STATE_NAME: &str = "ContractState";
@@ -13,13 +43,142 @@ pub const INTERFACE_ATTR: &str = "starknet::interface";
 pub(super) const DEPRECATED_CONTRACT_ATTR: &str = "contract";
 pub(super) const CONTRACT_ATTR: &str = "starknet::contract";
 pub const STORAGE_ATTR: &str = "storage";
+/// Generates a STORAGE_GETTER_FUNCTION view for the storage struct it annotates.
+pub(super) const STORAGE_GETTER_ATTR: &str = "storage_getter";
+/// Generates a STORAGE_RESET_FUNCTION function in test builds, zeroing the storage members of the
+/// storage struct it annotates.
+pub(super) const RESETTABLE_ATTR: &str = "resettable";
+/// Generates a STORAGE_MIGRATE_FUNCTION function for the storage struct it annotates, copying the
+/// values of the members of the given previous version of the struct to the members of the same
+/// name whose address changed.
+pub(super) const MIGRATE_FROM_ATTR: &str = "migrate_from";
+/// Marks the storage accessors of the storage struct it annotates as `#[inline(always)]`, saving
+/// the call overhead of the accessors on hot paths.
+pub(super) const INLINE_ACCESSORS_ATTR: &str = "inline_accessors";
+/// Generates a view returning the value of the storage member it annotates, named after the
+/// member with the GETTER_FUNCTION_PREFIX prefix.
+pub(super) const GETTER_ATTR: &str = "getter";
+pub(super) const GETTER_FUNCTION_PREFIX: &str = "get_";
+/// Selects the function hashing the keys of the mapping storage member it annotates into storage
+/// addresses, either `pedersen` (the default) or `poseidon`.
+pub(super) const HASH_ATTR: &str = "hash";
+/// Selects the handling of failed storage reads of the storage member it annotates, either `unwrap`
+/// (the default), panicking with the revert reason of the syscall, or `panic`, also marking the
+/// revert reason as a failed storage read.
+pub(super) const READ_FAILURE_ATTR: &str = "read_failure";
+/// Generates a `read_optional` accessor for the `LegacyMap` storage member it annotates, returning
+/// `None` for the entries whose first storage slot is zero. As storage doesn't track the presence
+/// of entries, entries written with a zero value also read as `None`.
+pub(super) const OPTIONAL_READS_ATTR: &str = "optional_reads";
+/// Pins the base address of the storage member it annotates to the given storage slot, instead of
+/// the `starknet_keccak` of its name.
+pub(super) const STORAGE_SLOT_ATTR: &str = "storage_slot";
+/// Marks a struct declared in the contract as a group of storage members. A storage member of its
+/// type is accessed field by field, e.g. `self.config.owner.read()`, and each field is stored at
+/// the Pedersen hash of the address of the member and the `starknet_keccak` of the field name.
+pub(super) const FLAT_ATTR: &str = "flat";
 pub const EXTERNAL_ATTR: &str = "external";
 pub const L1_HANDLER_ATTR: &str = "l1_handler";
 pub const CONSTRUCTOR_ATTR: &str = "constructor";
+pub(super) const FALLBACK_ATTR: &str = "fallback";
 pub(super) const RAW_OUTPUT_ATTR: &str = "raw_output";
+/// Keeps the gas check before the deserialization of the arguments in the wrapper of the entry
+/// point it annotates. The check may be omitted when the entry point takes no arguments, see
+/// `StarkNetPluginBuilder::with_minimal_wrappers`.
+pub(super) const KEEP_GAS_CHECK_ATTR: &str = "keep_gas_check";
+/// Marks an entry point whose trailing `Array::<felt252>` parameter receives all the remaining
+/// calldata. Unlike a regular array parameter, it is not prefixed by its length in the calldata,
+/// and its ABI input is of kind `rest`.
+pub const REST_ARGS_ATTR: &str = "rest_args";
+/// Makes the wrapper of the entry point it annotates check the length of the remaining calldata
+/// after each argument that is followed only by arguments of a known serialized size, failing with
+/// the name of the argument on a mismatch. Meant for debugging the encoding of clients.
+pub(super) const STRICT_ARGS_ATTR: &str = "strict_args";
+pub(super) const RESULT_WRAPPERS_ATTR: &str = "result_wrappers";
+/// Serializes the `felt252` returned by the entry point it annotates as an `Option::<felt252>`,
+/// which is `None` if the returned value is zero.
+pub const RETURNS_OPTIONAL_ATTR: &str = "returns_optional";
+/// Marks an external function taking `self` as a snapshot as mutating, for views that may also be
+/// invoked in transactions. IS_VIEW_FUNCTION reports it as not a view, its ABI state mutability is
+/// `external`, and unlike views it may write to storage. Its wrapper is the same as that of a view.
+pub const VIEW_EXTERNAL_ATTR: &str = "view_external";
+/// Makes the wrapper of the external function it annotates pass the result of
+/// `get_execution_info_syscall` as its first parameter after `self`, which must be a
+/// `starknet::info::ExecutionInfo`, instead of reading it from the calldata.
+pub const INJECT_EXECUTION_INFO_ATTR: &str = "inject_execution_info";
+/// Makes the wrapper of the external function it annotates fail with `'Not owner'` unless the
+/// caller is the address stored in the OWNER_MEMBER_NAME storage member.
+pub(super) const ONLY_OWNER_ATTR: &str = "only_owner";
+/// Generates the RECORDING_EXTERNAL_MODULE module in test builds, with wrappers of the external
+/// functions that emit their decoded arguments as an event before calling them.
+pub(super) const RECORD_ARGS_ATTR: &str = "record_args";
+/// Generates the SELECTORS_MODULE module, with a `const` item holding the selector of each entry
+/// point, named by the upper case name of the entry point.
+pub(super) const SELECTOR_CONSTS_ATTR: &str = "selector_consts";
+/// Generates the CALLDATA_MODULE module, with a struct holding the arguments of each entry point,
+/// named by the camel case name of the entry point followed by `Calldata`. The wrappers deserialize
+/// the arguments through these structs.
+pub(super) const CALLDATA_STRUCTS_ATTR: &str = "calldata_structs";
+/// Groups the entry point it annotates into the given interface, whose ABI trait lists only its
+/// entry points, instead of ABI_TRAIT.
+pub(super) const ENTRY_POINT_INTERFACE_ATTR: &str = "interface";
+/// Generates an impl of ABI_TRAIT that delegates to the entry points, and makes the wrappers call
+/// the entry points through it.
+pub(super) const ABI_IMPL_ATTR: &str = "abi_impl";
+/// Overrides the name of the generated external module, which is EXTERNAL_MODULE by default.
+pub(super) const EXTERNAL_MODULE_ATTR: &str = "external_module";
+/// Suppresses the missing constructor error for the contract it annotates, for contracts whose
+/// storage is intentionally zero initialized, see
+/// `StarkNetPluginBuilder::with_missing_constructor_lint`.
+pub(super) const ALLOW_MISSING_CONSTRUCTOR_ATTR: &str = "allow_missing_constructor";
+/// Generates an IS_VIEW_FUNCTION function, returning whether the external function of a given
+/// selector is a view, and panicking for unknown selectors.
+pub(super) const IS_VIEW_ATTR: &str = "is_view";
+/// Generates an ENTRY_POINTS_FUNCTION function, returning the selectors of the external functions.
+pub(super) const ENTRY_POINTS_ATTR: &str = "entry_points";
+/// Generates a SELECTOR_NAMES_FUNCTION function, mapping the selectors of the entry points to
+/// their names as short strings, for debugging.
+pub(super) const SELECTOR_NAMES_ATTR: &str = "selector_names";
+/// Overrides the string hashed into the selector of the entry point it annotates, which is the name
+/// of the entry point by default. The string is given as a short string literal.
+pub const SELECTOR_NAME_ATTR: &str = "selector_name";
+/// Generates a DEPLOY_FUNCTION function for the constructor it annotates, deploying the contract
+/// with the serialized constructor arguments as calldata.
+pub(super) const DEPLOY_HELPER_ATTR: &str = "deploy_helper";
+/// Declares an interface id supported by the contract it annotates, and may be repeated. Generates
+/// a SUPPORTS_INTERFACE_FUNCTION view, returning whether a given interface id was declared.
+pub(super) const SUPPORTS_ATTR: &str = "supports";
+/// Generates an UPGRADE_FUNCTION external entry point, replacing the class of the contract it
+/// annotates with the given class hash. If the contract is also annotated with ONLY_OWNER_ATTR,
+/// only the owner may upgrade it.
+pub(super) const UPGRADEABLE_ATTR: &str = "upgradeable";
+/// Generates a CONTRACT_NAME_CONST `felt252` const in the contract it annotates, holding the name
+/// of the contract as a short string.
+pub(super) const CONTRACT_NAME_CONST_ATTR: &str = "contract_name_const";
 
 pub(super) const L1_HANDLER_FIRST_PARAM_NAME: &str = "from_address";
 pub(super) const CALLDATA_PARAM_NAME: &str = "__calldata__";
+pub(super) const RECORDED_ARGS_NAME: &str = "__recorded_args__";
+/// The prefix of the names of the deserialized arguments in the entry point wrappers.
+pub(super) const WRAPPER_ARG_PREFIX: &str = "__arg_";
+pub(super) const OWNER_MEMBER_NAME: &str = "owner";
+
+/// The maximal length of a short string literal.
+pub(super) const SHORT_STRING_MAX_LENGTH: usize = 31;
+
+/// The maximal number of storage slots of a storage member, as the slots of a value are addressed
+/// by a `u8` offset from its base address.
+pub(super) const MAX_STORAGE_MEMBER_SLOTS: usize = 256;
+/// Identifiers that can't name the items the plugin generates from user defined names: the keywords
+/// of the language, and `super`, which would shadow the paths the generated code uses.
+pub(super) const RESERVED_IDENTIFIERS: [&str; 27] = [
+    "as", "break", "const", "continue", "else", "enum", "extern", "false", "fn", "if", "impl",
+    "implicits", "let", "loop", "match", "mod", "mut", "nopanic", "of", "ref", "return", "struct",
+    "super", "trait", "true", "type", "use",
+];
+
+/// The maximal depth of nested arrays in the parameters and return types of entry points.
+pub(super) const MAX_ARRAY_NESTING_DEPTH: usize = 4;
 
 /// Starknet OS required implicit precedence.
 pub(super) const IMPLICIT_PRECEDENCE: &[&str] = &[
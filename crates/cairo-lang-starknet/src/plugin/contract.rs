@@ -6,24 +6,57 @@ use cairo_lang_defs::plugin::{
 };
 use cairo_lang_semantic::patcher::{PatchBuilder, RewriteNode};
 use cairo_lang_semantic::plugin::DynPluginAuxData;
-use cairo_lang_syntax::node::ast::{MaybeModuleBody, OptionWrappedGenericParamList};
+use cairo_lang_syntax::attribute::structured::AttributeStructurize;
+use cairo_lang_syntax::node::ast::{
+    MaybeModuleBody, OptionArgListParenthesized, OptionWrappedGenericParamList,
+};
 use cairo_lang_syntax::node::db::SyntaxGroup;
 use cairo_lang_syntax::node::helpers::{GetIdentifier, QueryAttrs};
+use cairo_lang_syntax::node::ids::SyntaxStablePtrId;
 use cairo_lang_syntax::node::kind::SyntaxKind;
 use cairo_lang_syntax::node::{ast, Terminal, TypedSyntaxNode};
 use cairo_lang_utils::ordered_hash_map::OrderedHashMap;
+use cairo_lang_utils::unordered_hash_set::UnorderedHashSet;
 use indoc::formatdoc;
+use itertools::Itertools;
+use num_bigint::BigUint;
+use smol_str::SmolStr;
 
 use super::consts::{
-    ABI_TRAIT, CONSTRUCTOR_ATTR, CONSTRUCTOR_MODULE, CONTRACT_ATTR, DEPRECATED_CONTRACT_ATTR,
-    EVENT_ATTR, EVENT_TYPE_NAME, EXTERNAL_ATTR, EXTERNAL_MODULE, L1_HANDLER_ATTR,
-    L1_HANDLER_FIRST_PARAM_NAME, L1_HANDLER_MODULE, STORAGE_ATTR, STORAGE_STRUCT_NAME,
+    ABI_IMPL, ABI_IMPL_ATTR, ABI_TRAIT, ALLOW_MISSING_CONSTRUCTOR_ATTR, CALLDATA_MODULE,
+    CALLDATA_PARAM_NAME, CALLDATA_STRUCTS_ATTR, CONSTRUCTOR_ATTR, CONSTRUCTOR_MODULE, CONTRACT_ATTR,
+    CONTRACT_NAME_CONST, CONTRACT_NAME_CONST_ATTR, DEPLOY_FUNCTION, DEPLOY_HELPER_ATTR,
+    DEPRECATED_CONTRACT_ATTR, ENTRY_POINTS_ATTR, ENTRY_POINTS_FUNCTION, ENTRY_POINT_INTERFACE_ATTR, EVENT_ATTR,
+    EVENT_TYPE_NAME, EXTERNAL_ATTR, EXTERNAL_MODULE, EXTERNAL_MODULE_ATTR, FALLBACK_ATTR,
+    GENERATED_MODULE_NAMES, GETTER_ATTR, GETTER_FUNCTION_PREFIX, INJECT_EXECUTION_INFO_ATTR,
+    INTERFACE_ATTR, IS_VIEW_ATTR, IS_VIEW_FUNCTION, KEEP_GAS_CHECK_ATTR, L1_HANDLER_ATTR,
+    L1_HANDLER_FIRST_PARAM_NAME, L1_HANDLER_MODULE, MIGRATE_FROM_ATTR, ONLY_OWNER_ATTR,
+    OWNER_MEMBER_NAME, RAW_OUTPUT_ATTR, RECORDING_EXTERNAL_MODULE, RECORD_ARGS_ATTR,
+    RESETTABLE_ATTR, REST_ARGS_ATTR, RESULT_EXTERNAL_MODULE, RESULT_WRAPPERS_ATTR,
+    RETURNS_OPTIONAL_ATTR, SELECTORS_MODULE, SELECTOR_CONSTS_ATTR, SELECTOR_NAMES_ATTR,
+    SELECTOR_NAMES_FUNCTION, SELECTOR_NAME_ATTR, STORAGE_ATTR, STORAGE_GETTER_ATTR,
+    STORAGE_GETTER_FUNCTION, STORAGE_STRUCT_NAME, STRICT_ARGS_ATTR, SUPPORTS_ATTR,
+    SUPPORTS_INTERFACE_FUNCTION, UPGRADEABLE_ATTR, UPGRADE_FUNCTION, VIEW_EXTERNAL_ATTR,
+    WRAPPER_ARG_PREFIX,
+};
+use super::entry_point::{
+    arg_definition_code, entry_point_selector_name, generate_calldata_struct,
+    generate_entry_point_wrapper, generate_wrapper_code, has_external_attribute, owner_check_code,
+    validate_param_count, EntryPointKind,
+};
+use super::storage::{
+    collect_contract_structs, generate_member_getter, generate_storage_getter,
+    generate_storage_migration, generate_storage_reset, get_migrated_struct, handle_storage_struct,
+    migrated_struct_name,
+};
+use super::utils::{
+    forbid_attribute_args, is_contract_address, is_felt252, is_mut_param, is_ref_param,
+    maybe_strip_underscore, try_extract_felt252_literal, validate_name_not_reserved,
+    validate_short_string_length,
 };
-use super::entry_point::{generate_entry_point_wrapper, has_external_attribute, EntryPointKind};
-use super::storage::handle_storage_struct;
-use super::utils::{is_felt252, is_mut_param, maybe_strip_underscore};
-use crate::contract::starknet_keccak;
+use crate::contract::{selector_name_from_attr, starknet_keccak};
 use crate::plugin::aux_data::StarkNetContractAuxData;
+use crate::plugin::StarkNetPlugin;
 
 /// Handles a contract module item.
 pub fn handle_module(db: &dyn SyntaxGroup, module_ast: ast::ItemModule) -> PluginResult {
@@ -40,16 +73,17 @@ pub fn handle_module(db: &dyn SyntaxGroup, module_ast: ast::ItemModule) -> Plugi
             remove_original_item: false,
         };
     }
-    if !module_ast.has_attr(db, CONTRACT_ATTR) {
+    // Diagnostics about the contract as a whole are reported at its attribute.
+    let Some(contract_attr) = module_ast.find_attr(db, CONTRACT_ATTR) else {
         return PluginResult::default();
-    }
+    };
 
     let MaybeModuleBody::Some(body) = module_ast.body(db) else {
         return PluginResult {
             code: None,
             diagnostics: vec![PluginDiagnostic {
                 message: "Contracts without body are not supported.".to_string(),
-                stable_ptr: module_ast.stable_ptr().untyped(),
+                stable_ptr: contract_attr.stable_ptr().untyped(),
             }],
             remove_original_item: false,
         };
@@ -61,7 +95,7 @@ pub fn handle_module(db: &dyn SyntaxGroup, module_ast: ast::ItemModule) -> Plugi
             code: None,
             diagnostics: vec![PluginDiagnostic {
                 message: "Contracts must define a 'Storage' struct.".to_string(),
-                stable_ptr: module_ast.stable_ptr().untyped(),
+                stable_ptr: contract_attr.stable_ptr().untyped(),
             }],
             remove_original_item: false,
         };
@@ -85,16 +119,86 @@ pub fn handle_module(db: &dyn SyntaxGroup, module_ast: ast::ItemModule) -> Plugi
 #[derive(Default)]
 struct ContractGenerationData {
     generated_external_functions: Vec<RewriteNode>,
+    /// Wrappers of the external functions that return a `Result` instead of panicking. Only
+    /// generated for contracts annotated with RESULT_WRAPPERS_ATTR.
+    generated_result_external_functions: Option<Vec<RewriteNode>>,
+    /// Wrappers of the external functions that record their decoded arguments. Only generated
+    /// for contracts annotated with RECORD_ARGS_ATTR.
+    generated_recording_external_functions: Option<Vec<RewriteNode>>,
     generated_constructor_functions: Vec<RewriteNode>,
     generated_l1_handler_functions: Vec<RewriteNode>,
     abi_functions: Vec<RewriteNode>,
+    /// Functions of the ABI_IMPL impl of the ABI trait. Only generated for contracts annotated
+    /// with ABI_IMPL_ATTR.
+    abi_impl_functions: Option<Vec<RewriteNode>>,
+    /// The ABI traits of the interfaces declared by ENTRY_POINT_INTERFACE_ATTR, by interface name.
+    /// The entry points of an interface are listed in its trait instead of ABI_TRAIT.
+    interface_abis: OrderedHashMap<SmolStr, InterfaceAbi>,
     event_functions: Vec<RewriteNode>,
     abi_events: Vec<RewriteNode>,
+    /// The names of the entry points, including the views generated by the plugin.
+    entry_point_names: UnorderedHashSet<SmolStr>,
+    /// The strings hashed into the selectors of the entry points, which are their names unless
+    /// overridden by SELECTOR_NAME_ATTR.
+    entry_point_selector_names: UnorderedHashSet<SmolStr>,
+    /// The strings hashed into the selectors of the entry points annotated with
+    /// SELECTOR_NAME_ATTR, by entry point name.
+    selector_names: OrderedHashMap<SmolStr, SmolStr>,
+    /// The selectors of the external functions, and whether each of them is a view. The
+    /// IS_VIEW_FUNCTION and ENTRY_POINTS_FUNCTION functions are generated from them, for contracts
+    /// annotated with IS_VIEW_ATTR and ENTRY_POINTS_ATTR respectively.
+    external_selectors: Vec<(BigUint, bool)>,
+    /// The checks of the SELECTOR_NAMES_FUNCTION function, one per entry point. Only generated for
+    /// contracts annotated with SELECTOR_NAMES_ATTR.
+    selector_name_checks: Option<Vec<String>>,
+    /// The `const` items of the SELECTORS_MODULE module, one per entry point. Only generated for
+    /// contracts annotated with SELECTOR_CONSTS_ATTR.
+    selector_consts: Option<Vec<String>>,
+    /// The structs of the CALLDATA_MODULE module, one per entry point. Only generated for
+    /// contracts annotated with CALLDATA_STRUCTS_ATTR.
+    calldata_structs: Option<Vec<RewriteNode>>,
+    /// The DEPLOY_FUNCTION function. Only generated for constructors annotated with
+    /// DEPLOY_HELPER_ATTR.
+    deploy_function: Option<RewriteNode>,
+    /// The OWNER_MEMBER_NAME storage member, required by ONLY_OWNER_ATTR.
+    owner_member: OwnerMember,
+    /// The SUPPORTS_INTERFACE_FUNCTION function. Only generated for contracts annotated with
+    /// SUPPORTS_ATTR.
+    supports_interface_function: Option<RewriteNode>,
+    /// The UPGRADE_FUNCTION function. Only generated for contracts annotated with
+    /// UPGRADEABLE_ATTR.
+    upgrade_function: Option<RewriteNode>,
+    /// Whether the panicking wrappers fail with `panic_with_felt252` when possible, see
+    /// `StarkNetPluginBuilder::with_felt252_panics`.
+    felt252_panics: bool,
+    /// Whether the gas check before the deserialization of the arguments is omitted from the
+    /// wrappers of entry points without arguments, see
+    /// `StarkNetPluginBuilder::with_minimal_wrappers`.
+    minimal_wrappers: bool,
+}
+
+/// The OWNER_MEMBER_NAME storage member of a contract, see [validate_owner_member].
+#[derive(Default)]
+enum OwnerMember {
+    #[default]
+    Missing,
+    /// A member of a type other than `ContractAddress`, reported on its type at most once.
+    NotContractAddress { type_ptr: SyntaxStablePtrId, reported: bool },
+    Valid,
+}
+
+/// The ABI trait of an interface declared by ENTRY_POINT_INTERFACE_ATTR.
+#[derive(Default)]
+struct InterfaceAbi {
+    functions: Vec<RewriteNode>,
+    /// Functions of the impl of the trait, generated along with ABI_IMPL.
+    impl_functions: Vec<RewriteNode>,
 }
 
 /// If the module is annotated with CONTRACT_ATTR, generate the relevant contract logic.
 pub fn handle_contract_by_storage(
     db: &dyn SyntaxGroup,
+    plugin: &StarkNetPlugin,
     struct_ast: ast::ItemStruct,
 ) -> Option<PluginResult> {
     let module_node = struct_ast.as_syntax_node().parent()?.parent()?.parent()?;
@@ -122,13 +226,44 @@ pub fn handle_contract_by_storage(
     };
     let mut diagnostics = vec![];
     let mut kept_original_items = Vec::new();
+    let external_module_name = get_external_module_name(db, &mut diagnostics, &module_ast);
+    forbid_attribute_args(
+        db,
+        &mut diagnostics,
+        &module_ast,
+        &[
+            RESULT_WRAPPERS_ATTR,
+            ABI_IMPL_ATTR,
+            IS_VIEW_ATTR,
+            ENTRY_POINTS_ATTR,
+            SELECTOR_NAMES_ATTR,
+            SELECTOR_CONSTS_ATTR,
+            CALLDATA_STRUCTS_ATTR,
+            ALLOW_MISSING_CONSTRUCTOR_ATTR,
+            RECORD_ARGS_ATTR,
+            UPGRADEABLE_ATTR,
+            ONLY_OWNER_ATTR,
+            CONTRACT_NAME_CONST_ATTR,
+        ],
+    );
+    // Duplicated contract attributes usually signal a bug, e.g. in attribute merging macros.
+    for attr in module_ast.query_attr(db, CONTRACT_ATTR).into_iter().skip(1) {
+        diagnostics.push(PluginDiagnostic {
+            message: format!("The '{CONTRACT_ATTR}' attribute is duplicated."),
+            stable_ptr: attr.stable_ptr().untyped(),
+        });
+    }
 
+    // The previous version of the storage struct only describes the layout to migrate from.
+    let migrated_struct_name = migrated_storage_struct_name(db, &body);
     // A mapping from a 'use' item to its path.
     let mut extra_uses = OrderedHashMap::default();
     let mut has_event = false;
     for item in body.items(db).elements(db) {
         // Skipping elements that only generate other code, but their code itself is ignored.
-        if matches!(&item, ast::Item::Struct(item) if item.name(db).text(db) == STORAGE_STRUCT_NAME)
+        if matches!(&item, ast::Item::Struct(item)
+            if item.name(db).text(db) == STORAGE_STRUCT_NAME
+                || Some(item.name(db).text(db).as_str()) == migrated_struct_name.as_deref())
         {
             continue;
         }
@@ -212,6 +347,8 @@ pub fn handle_contract_by_storage(
     for (use_item, path) in [
         ("ClassHashSerde", "starknet::class_hash::ClassHashSerde"),
         ("ContractAddressSerde", "starknet::contract_address::ContractAddressSerde"),
+        ("EthAddressSerde", "starknet::eth_address::EthAddressSerde"),
+        ("Secp256k1PointSerde", "starknet::secp256k1::Secp256k1PointSerde"),
         ("StorageAddressSerde", "starknet::storage_access::StorageAddressSerde"),
         ("OptionTrait", "option::OptionTrait"),
         ("OptionTraitImpl", "option::OptionTraitImpl"),
@@ -227,18 +364,92 @@ pub fn handle_contract_by_storage(
             .map(|use_path| RewriteNode::Text(format!("\n        use {use_path};")))
             .collect(),
     );
+    // The modules of the storage members are nested in STORAGE_MODULE, so the items of the
+    // contract are one more level up from them.
+    let storage_extra_uses_node = RewriteNode::new_modified(
+        extra_uses
+            .values()
+            .map(|use_path| {
+                let use_path = match use_path.strip_prefix("super::") {
+                    Some(item_path) => format!("super::super::{item_path}"),
+                    None => use_path.clone(),
+                };
+                RewriteNode::Text(format!("\n        use {use_path};"))
+            })
+            .collect(),
+    );
 
-    let mut data = ContractGenerationData::default();
+    let mut data = ContractGenerationData {
+        generated_result_external_functions: module_ast
+            .has_attr(db, RESULT_WRAPPERS_ATTR)
+            .then(Vec::new),
+        generated_recording_external_functions: module_ast
+            .has_attr(db, RECORD_ARGS_ATTR)
+            .then(Vec::new),
+        abi_impl_functions: module_ast.has_attr(db, ABI_IMPL_ATTR).then(Vec::new),
+        selector_name_checks: module_ast.has_attr(db, SELECTOR_NAMES_ATTR).then(Vec::new),
+        selector_consts: module_ast.has_attr(db, SELECTOR_CONSTS_ATTR).then(Vec::new),
+        calldata_structs: module_ast.has_attr(db, CALLDATA_STRUCTS_ATTR).then(Vec::new),
+        owner_member: match struct_ast
+            .members(db)
+            .elements(db)
+            .into_iter()
+            .find(|member| member.name(db).text(db) == OWNER_MEMBER_NAME)
+        {
+            Some(member) if is_contract_address(db, &member.type_clause(db).ty(db)) => {
+                OwnerMember::Valid
+            }
+            Some(member) => OwnerMember::NotContractAddress {
+                type_ptr: member.type_clause(db).ty(db).stable_ptr().untyped(),
+                reported: false,
+            },
+            None => OwnerMember::Missing,
+        },
+        felt252_panics: plugin.felt252_panics,
+        minimal_wrappers: plugin.minimal_wrappers,
+        ..Default::default()
+    };
 
+    if let Some(attr) = module_ast.find_attr(db, SUPPORTS_ATTR) {
+        data.supports_interface_function =
+            Some(generate_supports_interface_function(db, &mut diagnostics, &module_ast));
+        add_generated_view_entry_point(
+            &mut data,
+            &mut diagnostics,
+            attr.stable_ptr().untyped(),
+            SUPPORTS_INTERFACE_FUNCTION,
+            &[("interface_id", "felt252")],
+            RewriteNode::Text("bool".to_string()),
+            false,
+        );
+    }
+    let contract_structs =
+        collect_contract_structs(db, &mut diagnostics, &body.items(db).elements(db));
     let mut storage_code = RewriteNode::Text("".to_string());
+    let mut storage_layout = vec![];
+    let mut has_constructor = false;
     for item in body.items(db).elements(db) {
         match &item {
             ast::Item::FreeFunction(item_function) => {
+                report_fallback_attr(db, &mut diagnostics, item_function);
                 let Some(entry_point_kind) =
                     EntryPointKind::try_from_function_with_body(db, &mut diagnostics, item_function)
                 else {
                     continue;
                 };
+                has_constructor |= entry_point_kind == EntryPointKind::Constructor;
+                // A missing body is already a parse error, but the wrapper would add errors
+                // unrelated to it.
+                if item_function.body(db).lbrace(db).text(db).is_empty() {
+                    diagnostics.push(PluginDiagnostic {
+                        message: format!(
+                            "Entry points must have a body. The entry points of other contracts \
+                             are declared in traits annotated with #[{INTERFACE_ATTR}]."
+                        ),
+                        stable_ptr: item_function.declaration(db).name(db).stable_ptr().untyped(),
+                    });
+                    continue;
+                }
                 let function_name = RewriteNode::new_trimmed(
                     item_function.declaration(db).name(db).as_syntax_node(),
                 );
@@ -249,25 +460,87 @@ pub fn handle_contract_by_storage(
                     db,
                     &mut diagnostics,
                     &mut data,
+                    plugin,
                 );
             }
-            ast::Item::Impl(item_impl) => {
-                if !has_external_attribute(db, &mut diagnostics, &item) {
-                    continue;
+            ast::Item::ExternFunction(extern_function) => {
+                for attr_name in [EXTERNAL_ATTR, L1_HANDLER_ATTR, CONSTRUCTOR_ATTR] {
+                    if let Some(attr) = extern_function.find_attr(db, attr_name) {
+                        diagnostics.push(PluginDiagnostic {
+                            message: "Entry points must be implemented in the contract, so \
+                                      `extern` functions can't be entry points."
+                                .to_string(),
+                            stable_ptr: attr.stable_ptr().untyped(),
+                        });
+                    }
                 }
+            }
+            ast::Item::Impl(item_impl) => {
+                let is_external_impl = has_external_attribute(db, &mut diagnostics, &item);
                 let ast::MaybeImplBody::Some(body) = item_impl.body(db) else { continue; };
                 let impl_name = RewriteNode::new_trimmed(item_impl.name(db).as_syntax_node());
+                let mut reported_generic_impl = false;
                 for item in body.items(db).elements(db) {
-                    forbid_attribute_in_external_impl(db, &mut diagnostics, &item, EXTERNAL_ATTR);
-                    forbid_attribute_in_external_impl(
-                        db,
-                        &mut diagnostics,
-                        &item,
-                        CONSTRUCTOR_ATTR,
-                    );
-                    forbid_attribute_in_external_impl(db, &mut diagnostics, &item, L1_HANDLER_ATTR);
+                    report_fallback_attr(db, &mut diagnostics, &item);
+                    if is_external_impl {
+                        forbid_attribute_in_external_impl(
+                            db,
+                            &mut diagnostics,
+                            &item,
+                            EXTERNAL_ATTR,
+                        );
+                        forbid_attribute_in_external_impl(
+                            db,
+                            &mut diagnostics,
+                            &item,
+                            CONSTRUCTOR_ATTR,
+                        );
+                        forbid_attribute_in_external_impl(
+                            db,
+                            &mut diagnostics,
+                            &item,
+                            L1_HANDLER_ATTR,
+                        );
+                    } else if !has_external_attribute(db, &mut diagnostics, &item) {
+                        // Only the methods marked as external are entry points of a
+                        // non-external impl.
+                        continue;
+                    }
+
+                    // The ABI of impl entry points is taken from their trait, so attributes
+                    // changing the ABI of an entry point are not supported there.
+                    for attr_name in [
+                        SELECTOR_NAME_ATTR,
+                        RETURNS_OPTIONAL_ATTR,
+                        VIEW_EXTERNAL_ATTR,
+                        INJECT_EXECUTION_INFO_ATTR,
+                    ] {
+                        if let Some(attr) = item.find_attr(db, attr_name) {
+                            diagnostics.push(PluginDiagnostic {
+                                message: format!(
+                                    "The '{attr_name}' attribute is only supported on entry \
+                                     points that are free functions."
+                                ),
+                                stable_ptr: attr.stable_ptr().untyped(),
+                            });
+                        }
+                    }
 
                     let ast::ImplItem::Function(item_function) = item else { continue; };
+                    if let OptionWrappedGenericParamList::WrappedGenericParamList(generic_params) =
+                        item_impl.generic_params(db)
+                    {
+                        if !reported_generic_impl {
+                            diagnostics.push(PluginDiagnostic {
+                                message: "Impls containing contract entry points cannot have \
+                                          generic arguments."
+                                    .to_string(),
+                                stable_ptr: generic_params.stable_ptr().untyped(),
+                            });
+                            reported_generic_impl = true;
+                        }
+                        continue;
+                    }
                     let function_name = RewriteNode::new_trimmed(
                         item_function.declaration(db).name(db).as_syntax_node(),
                     );
@@ -286,22 +559,354 @@ pub fn handle_contract_by_storage(
                         db,
                         &mut diagnostics,
                         &mut data,
+                        plugin,
                     );
                 }
             }
             ast::Item::Struct(item_struct)
                 if item_struct.name(db).text(db) == STORAGE_STRUCT_NAME =>
             {
-                let (storage_rewrite_node, storage_diagnostics) =
-                    handle_storage_struct(db, item_struct.clone(), &extra_uses_node, has_event);
+                let (storage_rewrite_node, storage_diagnostics, storage_member_layouts) =
+                    handle_storage_struct(
+                        db,
+                        item_struct.clone(),
+                        &contract_structs,
+                        &storage_extra_uses_node,
+                        has_event,
+                    );
                 storage_code = storage_rewrite_node;
                 diagnostics.extend(storage_diagnostics);
+                storage_layout = storage_member_layouts;
+                if let Some(attr) = item_struct.find_attr(db, STORAGE_GETTER_ATTR) {
+                    storage_code = RewriteNode::new_modified(vec![
+                        storage_code,
+                        generate_storage_getter(db, item_struct, &contract_structs),
+                    ]);
+                    add_generated_view_entry_point(
+                        &mut data,
+                        &mut diagnostics,
+                        attr.stable_ptr().untyped(),
+                        STORAGE_GETTER_FUNCTION,
+                        &[],
+                        RewriteNode::Text("Span::<felt252>".to_string()),
+                        true,
+                    );
+                }
+                if let Some(migrated_struct) = get_migrated_struct(
+                    db,
+                    &mut diagnostics,
+                    item_struct,
+                    &body.items(db).elements(db),
+                ) {
+                    storage_code = RewriteNode::new_modified(vec![
+                        storage_code,
+                        generate_storage_migration(
+                            db,
+                            &mut diagnostics,
+                            item_struct,
+                            &migrated_struct,
+                            &contract_structs,
+                        ),
+                    ]);
+                }
+                if item_struct.has_attr(db, RESETTABLE_ATTR) {
+                    storage_code = RewriteNode::new_modified(vec![
+                        storage_code,
+                        generate_storage_reset(db, item_struct, &contract_structs),
+                    ]);
+                }
+                for member in item_struct.members(db).elements(db) {
+                    if !member.has_attr(db, GETTER_ATTR) {
+                        continue;
+                    }
+                    forbid_attribute_args(db, &mut diagnostics, &member, &[GETTER_ATTR]);
+                    let Some(getter_code) =
+                        generate_member_getter(db, &mut diagnostics, &member, &contract_structs)
+                    else {
+                        continue;
+                    };
+                    storage_code = RewriteNode::new_modified(vec![storage_code, getter_code]);
+                    let name = member.name(db);
+                    add_generated_view_entry_point(
+                        &mut data,
+                        &mut diagnostics,
+                        name.stable_ptr().untyped(),
+                        &format!("{GETTER_FUNCTION_PREFIX}{}", name.text(db)),
+                        &[],
+                        RewriteNode::new_trimmed(member.type_clause(db).ty(db).as_syntax_node()),
+                        false,
+                    );
+                }
             }
             _ => {}
         }
     }
 
+    let upgradeable_attr = module_ast.find_attr(db, UPGRADEABLE_ATTR);
+    // Checked against the entry points, including the methods of impls, and against the other free
+    // functions, as the generated function is declared in the contract module.
+    let declares_upgrade_function = data.entry_point_names.contains(UPGRADE_FUNCTION)
+        || body.items(db).elements(db).iter().any(|item| {
+            matches!(item, ast::Item::FreeFunction(function)
+                if function.declaration(db).name(db).text(db) == UPGRADE_FUNCTION)
+        });
+    if let Some(attr) = upgradeable_attr.as_ref().filter(|_| declares_upgrade_function) {
+        diagnostics.push(PluginDiagnostic {
+            message: format!(
+                "The contract already declares a function named `{UPGRADE_FUNCTION}`, which \
+                 would collide with the entry point generated by the '{UPGRADEABLE_ATTR}' \
+                 attribute."
+            ),
+            stable_ptr: attr.stable_ptr().untyped(),
+        });
+    } else if let Some(attr) = upgradeable_attr {
+        data.upgrade_function = Some(RewriteNode::Text(format!(
+            "
+
+#[{EXTERNAL_ATTR}(v0)]
+fn {UPGRADE_FUNCTION}(ref self: ContractState, new_class_hash: starknet::ClassHash) {{
+    starknet::replace_class_syscall(new_class_hash).unwrap_syscall();
+}}"
+        )));
+        let only_owner =
+            validate_contract_only_owner_attr(db, &mut diagnostics, &module_ast, &mut data);
+        add_generated_entry_point(
+            &mut data,
+            &mut diagnostics,
+            attr.stable_ptr().untyped(),
+            GeneratedEntryPoint {
+                name: UPGRADE_FUNCTION,
+                params: &[("new_class_hash", "starknet::ClassHash")],
+                return_type: None,
+                is_view: false,
+                raw_output: false,
+                only_owner,
+            },
+        );
+    } else if let Some(attr) = module_ast.find_attr(db, ONLY_OWNER_ATTR) {
+        diagnostics.push(PluginDiagnostic {
+            message: format!(
+                "The '{ONLY_OWNER_ATTR}' attribute is only supported on contracts annotated with \
+                 '{UPGRADEABLE_ATTR}'."
+            ),
+            stable_ptr: attr.stable_ptr().untyped(),
+        });
+    }
+
+    if plugin.default_constructor
+        && !has_constructor
+        && !report_colliding_item(
+            db,
+            &mut diagnostics,
+            &body,
+            "constructor",
+            "the generated default constructor",
+        )
+    {
+        storage_code =
+            RewriteNode::new_modified(vec![storage_code, add_default_constructor(&mut data)]);
+    }
+
+    if plugin.lint_missing_constructor
+        && !has_constructor
+        && !module_ast.has_attr(db, ALLOW_MISSING_CONSTRUCTOR_ATTR)
+        && !struct_ast.members(db).elements(db).is_empty()
+    {
+        diagnostics.push(PluginDiagnostic {
+            message: format!(
+                "Contracts with storage members should have a constructor initializing them. \
+                 Use #[{ALLOW_MISSING_CONSTRUCTOR_ATTR}] on the contract if zero initialized \
+                 storage is intended."
+            ),
+            stable_ptr: struct_ast.name(db).stable_ptr().untyped(),
+        });
+    }
+
+    let result_external_module = match data.generated_result_external_functions {
+        Some(generated_result_external_functions) => RewriteNode::interpolate_patched(
+            &formatdoc!(
+                "
+
+                mod {RESULT_EXTERNAL_MODULE} {{$extra_uses$
+
+                    $generated_result_external_functions$
+                }}"
+            ),
+            [
+                ("extra_uses".to_string(), extra_uses_node.clone()),
+                (
+                    "generated_result_external_functions".to_string(),
+                    RewriteNode::new_modified(generated_result_external_functions),
+                ),
+            ]
+            .into(),
+        ),
+        None => RewriteNode::Text("".to_string()),
+    };
+
+    let recording_external_module = match data.generated_recording_external_functions {
+        Some(generated_recording_external_functions) => RewriteNode::interpolate_patched(
+            &formatdoc!(
+                "
+
+                #[cfg(test)]
+                mod {RECORDING_EXTERNAL_MODULE} {{$extra_uses$
+
+                    $generated_recording_external_functions$
+                }}"
+            ),
+            [
+                ("extra_uses".to_string(), extra_uses_node.clone()),
+                (
+                    "generated_recording_external_functions".to_string(),
+                    RewriteNode::new_modified(generated_recording_external_functions),
+                ),
+            ]
+            .into(),
+        ),
+        None => RewriteNode::Text("".to_string()),
+    };
+
+    let selectors_module = match data.selector_consts {
+        Some(selector_consts) => RewriteNode::Text(format!(
+            "
+
+mod {SELECTORS_MODULE} {{{}
+}}",
+            selector_consts.join("")
+        )),
+        None => RewriteNode::Text("".to_string()),
+    };
+
+    let calldata_module = match data.calldata_structs {
+        Some(calldata_structs) => RewriteNode::interpolate_patched(
+            &formatdoc!(
+                "
+
+                mod {CALLDATA_MODULE} {{$extra_uses$
+                    $calldata_structs$
+                }}"
+            ),
+            [
+                ("extra_uses".to_string(), extra_uses_node.clone()),
+                ("calldata_structs".to_string(), RewriteNode::new_modified(calldata_structs)),
+            ]
+            .into(),
+        ),
+        None => RewriteNode::Text("".to_string()),
+    };
+
+    let has_abi_impl = data.abi_impl_functions.is_some();
+    let interface_abis = RewriteNode::new_modified(
+        data.interface_abis
+            .into_iter()
+            .map(|(interface, interface_abi)| {
+                generate_interface_abi(&interface, interface_abi, has_abi_impl)
+            })
+            .collect(),
+    );
+    let abi_impl = match data.abi_impl_functions {
+        Some(abi_impl_functions) => RewriteNode::interpolate_patched(
+            &formatdoc!(
+                "
+
+                impl {ABI_IMPL} of {ABI_TRAIT}<ContractState> {{
+                    $abi_impl_functions$
+                }}"
+            ),
+            [("abi_impl_functions".to_string(), RewriteNode::new_modified(abi_impl_functions))]
+                .into(),
+        ),
+        None => RewriteNode::Text("".to_string()),
+    };
+
+    let is_view_function = if module_ast.has_attr(db, IS_VIEW_ATTR)
+        && !report_colliding_item(
+            db,
+            &mut diagnostics,
+            &body,
+            IS_VIEW_FUNCTION,
+            &format!("the function generated by the '{IS_VIEW_ATTR}' attribute"),
+        ) {
+        let is_view_checks = data
+            .external_selectors
+            .iter()
+            .map(|(selector, is_view)| {
+                format!(
+                    "
+    if selector == 0x{selector:x} {{
+        return {is_view};
+    }}"
+                )
+            })
+            .join("");
+        RewriteNode::Text(format!(
+            "
+
+fn {IS_VIEW_FUNCTION}(selector: felt252) -> bool {{{is_view_checks}
+    panic_with_felt252('Unknown selector')
+}}"
+        ))
+    } else {
+        RewriteNode::Text("".to_string())
+    };
+    let entry_points_function = if module_ast.has_attr(db, ENTRY_POINTS_ATTR)
+        && !report_colliding_item(
+            db,
+            &mut diagnostics,
+            &body,
+            ENTRY_POINTS_FUNCTION,
+            &format!("the function generated by the '{ENTRY_POINTS_ATTR}' attribute"),
+        ) {
+        let selector_appends = data
+            .external_selectors
+            .iter()
+            .map(|(selector, _)| {
+                format!("\n    array::ArrayTrait::append(ref selectors, 0x{selector:x});")
+            })
+            .join("");
+        RewriteNode::Text(format!(
+            "
+
+fn {ENTRY_POINTS_FUNCTION}() -> Array<felt252> {{
+    let mut selectors = array::ArrayTrait::new();{selector_appends}
+    selectors
+}}"
+        ))
+    } else {
+        RewriteNode::Text("".to_string())
+    };
+
+    let selector_names_function = match data.selector_name_checks {
+        Some(selector_name_checks) => RewriteNode::Text(format!(
+            "
+
+fn {SELECTOR_NAMES_FUNCTION}(selector: felt252) -> felt252 {{{}
+    panic_with_felt252('Unknown selector')
+}}",
+            selector_name_checks.join("")
+        )),
+        None => RewriteNode::Text("".to_string()),
+    };
+
     let module_name_ast = module_ast.name(db);
+    let contract_name_const = match module_ast.find_attr(db, CONTRACT_NAME_CONST_ATTR) {
+        Some(attr) => {
+            let contract_name = module_name_ast.text(db);
+            if validate_short_string_length(
+                &mut diagnostics,
+                &contract_name,
+                "Contract names",
+                CONTRACT_NAME_CONST_ATTR,
+                attr.stable_ptr().untyped(),
+            ) {
+                format!("\nconst {CONTRACT_NAME_CONST}: felt252 = '{contract_name}';")
+            } else {
+                "".to_string()
+            }
+        }
+        None => "".to_string(),
+    };
     let test_class_hash = starknet_keccak(
         module_ast.as_syntax_node().get_text_without_trivia(db).as_str().as_bytes(),
     );
@@ -312,7 +917,7 @@ pub fn handle_contract_by_storage(
             use starknet::SyscallResultTraitImpl;
 
             #[cfg(test)]
-            const TEST_CLASS_HASH: felt252 = {test_class_hash};
+            const TEST_CLASS_HASH: felt252 = {test_class_hash};{contract_name_const}
             $storage_code$
 
             $event_functions$
@@ -320,9 +925,11 @@ pub fn handle_contract_by_storage(
             trait {ABI_TRAIT}<ContractState> {{
                 $abi_functions$
                 $abi_events$
-            }}
+            }}$abi_impl$$interface_abis$$is_view_function$$entry_points_function$\
+             $selector_names_function$$deploy_function$$supports_interface_function$\
+             $upgrade_function$
 
-            mod {EXTERNAL_MODULE} {{$extra_uses$
+            mod {external_module_name} {{$extra_uses$
 
                 $generated_external_functions$
             }}
@@ -335,7 +942,8 @@ pub fn handle_contract_by_storage(
             mod {CONSTRUCTOR_MODULE} {{$extra_uses$
 
                 $generated_constructor_functions$
-            }}
+            }}$result_external_module$$recording_external_module$$selectors_module$\
+             $calldata_module$
         "
         )
         .as_str(),
@@ -349,7 +957,29 @@ pub fn handle_contract_by_storage(
             ("event_functions".to_string(), RewriteNode::new_modified(data.event_functions)),
             ("abi_functions".to_string(), RewriteNode::new_modified(data.abi_functions)),
             ("abi_events".to_string(), RewriteNode::new_modified(data.abi_events)),
+            ("abi_impl".to_string(), abi_impl),
+            ("interface_abis".to_string(), interface_abis),
+            ("is_view_function".to_string(), is_view_function),
+            ("entry_points_function".to_string(), entry_points_function),
+            ("selector_names_function".to_string(), selector_names_function),
+            (
+                "deploy_function".to_string(),
+                data.deploy_function.unwrap_or_else(|| RewriteNode::Text("".to_string())),
+            ),
+            (
+                "supports_interface_function".to_string(),
+                data.supports_interface_function
+                    .unwrap_or_else(|| RewriteNode::Text("".to_string())),
+            ),
+            (
+                "upgrade_function".to_string(),
+                data.upgrade_function.unwrap_or_else(|| RewriteNode::Text("".to_string())),
+            ),
             ("extra_uses".to_string(), extra_uses_node),
+            ("result_external_module".to_string(), result_external_module),
+            ("recording_external_module".to_string(), recording_external_module),
+            ("selectors_module".to_string(), selectors_module),
+            ("calldata_module".to_string(), calldata_module),
             (
                 "generated_external_functions".to_string(),
                 RewriteNode::new_modified(data.generated_external_functions),
@@ -368,6 +998,18 @@ pub fn handle_contract_by_storage(
 
     let mut builder = PatchBuilder::new(db);
     builder.add_modified(generated_contract_mod);
+    if let Some(max_size) = plugin.max_generated_contract_size {
+        let size = builder.code.len();
+        if size > max_size {
+            diagnostics.push(PluginDiagnostic {
+                message: format!(
+                    "The generated code of the contract is {size} bytes long, exceeding the \
+                     limit of {max_size} bytes. Consider splitting the contract."
+                ),
+                stable_ptr: module_name_ast.stable_ptr().untyped(),
+            });
+        }
+    }
     Some(PluginResult {
         code: Some(PluginGeneratedFile {
             name: "contract".into(),
@@ -376,6 +1018,9 @@ pub fn handle_contract_by_storage(
                 StarkNetContractAuxData {
                     patches: builder.patches,
                     contracts: vec![module_name_ast.text(db)],
+                    external_module_name,
+                    selector_names: data.selector_names,
+                    storage_layout,
                 },
             )),
         }),
@@ -384,6 +1029,83 @@ pub fn handle_contract_by_storage(
     })
 }
 
+/// Returns the name of the struct that the MIGRATE_FROM_ATTR attribute of the storage struct of the
+/// contract refers to, if any.
+fn migrated_storage_struct_name(db: &dyn SyntaxGroup, body: &ast::ModuleBody) -> Option<String> {
+    body.items(db).elements(db).into_iter().find_map(|item| match item {
+        ast::Item::Struct(item) if item.name(db).text(db) == STORAGE_STRUCT_NAME => item
+            .find_attr(db, MIGRATE_FROM_ATTR)
+            .and_then(|attr| migrated_struct_name(db, &attr)),
+        _ => None,
+    })
+}
+
+/// Returns whether the struct is the previous version of the storage struct of a contract, named
+/// by its MIGRATE_FROM_ATTR attribute. Like the storage struct, such a struct is removed, as it only
+/// describes the storage layout.
+pub fn is_migrated_storage_struct(db: &dyn SyntaxGroup, struct_ast: &ast::ItemStruct) -> bool {
+    let Some(module_node) =
+        struct_ast.as_syntax_node().parent().and_then(|node| node.parent()?.parent()) else {
+        return false;
+    };
+    if module_node.kind(db) != SyntaxKind::ItemModule {
+        return false;
+    }
+    let module_ast = ast::ItemModule::from_syntax_node(db, module_node);
+    let MaybeModuleBody::Some(body) = module_ast.body(db) else {
+        return false;
+    };
+    module_ast.has_attr(db, CONTRACT_ATTR)
+        && migrated_storage_struct_name(db, &body).as_deref()
+            == Some(struct_ast.name(db).text(db).as_str())
+}
+
+/// Returns the name of the generated external module. Defaults to EXTERNAL_MODULE, and can be
+/// overridden by annotating the contract module with `#[external_module(name)]`.
+fn get_external_module_name(
+    db: &dyn SyntaxGroup,
+    diagnostics: &mut Vec<PluginDiagnostic>,
+    module_ast: &ast::ItemModule,
+) -> SmolStr {
+    let Some(attr) = module_ast.find_attr(db, EXTERNAL_MODULE_ATTR) else {
+        return EXTERNAL_MODULE.into();
+    };
+    let name = match attr.arguments(db) {
+        OptionArgListParenthesized::ArgListParenthesized(args) => {
+            match &args.args(db).elements(db)[..] {
+                [arg] => Some(arg.as_syntax_node().get_text_without_trivia(db)),
+                _ => None,
+            }
+        }
+        OptionArgListParenthesized::Empty(_) => None,
+    };
+    match name {
+        Some(name) if is_identifier(&name) && !is_reserved_module_name(&name) => name.into(),
+        _ => {
+            diagnostics.push(PluginDiagnostic {
+                message: format!(
+                    "`{EXTERNAL_MODULE_ATTR}` expects a single identifier argument that is not \
+                     the name of another generated module."
+                ),
+                stable_ptr: attr.stable_ptr().untyped(),
+            });
+            EXTERNAL_MODULE.into()
+        }
+    }
+}
+
+/// Checks if the given text is a plain identifier.
+fn is_identifier(text: &str) -> bool {
+    let mut chars = text.chars();
+    matches!(chars.next(), Some(c) if c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+/// Checks if the given name is used by one of the other modules generated for a contract.
+fn is_reserved_module_name(name: &str) -> bool {
+    GENERATED_MODULE_NAMES.contains(&name)
+}
+
 fn forbid_attribute_in_external_impl(
     db: &dyn SyntaxGroup,
     diagnostics: &mut Vec<PluginDiagnostic>,
@@ -400,6 +1122,24 @@ fn forbid_attribute_in_external_impl(
     }
 }
 
+/// Reports FALLBACK_ATTR on a function of the contract, whether a free function or an impl
+/// function, as Sierra contract classes have no default entry point.
+fn report_fallback_attr(
+    db: &dyn SyntaxGroup,
+    diagnostics: &mut Vec<PluginDiagnostic>,
+    function: &impl QueryAttrs,
+) {
+    if let Some(attr) = function.find_attr(db, FALLBACK_ATTR) {
+        diagnostics.push(PluginDiagnostic {
+            message: format!(
+                "The '{FALLBACK_ATTR}' attribute is not supported, Sierra contract classes have no \
+                 default entry point for unknown selectors."
+            ),
+            stable_ptr: attr.stable_ptr().untyped(),
+        });
+    }
+}
+
 /// Handles a contract entrypoint function.
 fn handle_entry_point(
     entry_point_kind: EntryPointKind,
@@ -408,10 +1148,96 @@ fn handle_entry_point(
     db: &dyn SyntaxGroup,
     diagnostics: &mut Vec<PluginDiagnostic>,
     data: &mut ContractGenerationData,
+    plugin: &StarkNetPlugin,
 ) {
     let attr = entry_point_kind.get_attr();
+    forbid_attribute_args(
+        db,
+        diagnostics,
+        item_function,
+        &[
+            CONSTRUCTOR_ATTR,
+            L1_HANDLER_ATTR,
+            RAW_OUTPUT_ATTR,
+            REST_ARGS_ATTR,
+            DEPLOY_HELPER_ATTR,
+            ONLY_OWNER_ATTR,
+            KEEP_GAS_CHECK_ATTR,
+            VIEW_EXTERNAL_ATTR,
+            STRICT_ARGS_ATTR,
+            INJECT_EXECUTION_INFO_ATTR,
+        ],
+    );
+    // The owner check is only generated when valid, to avoid follow-up errors in generated code.
+    let mut only_owner = false;
+    if let Some(only_owner_attr) = item_function.find_attr(db, ONLY_OWNER_ATTR) {
+        if entry_point_kind != EntryPointKind::External {
+            diagnostics.push(PluginDiagnostic {
+                message: format!(
+                    "The '{ONLY_OWNER_ATTR}' attribute is only supported on external functions."
+                ),
+                stable_ptr: only_owner_attr.stable_ptr().untyped(),
+            });
+        } else {
+            only_owner =
+                validate_owner_member(diagnostics, data, only_owner_attr.stable_ptr().untyped());
+        }
+    }
+    if let Some(inject_execution_info_attr) =
+        item_function.find_attr(db, INJECT_EXECUTION_INFO_ATTR)
+    {
+        if entry_point_kind != EntryPointKind::External {
+            diagnostics.push(PluginDiagnostic {
+                message: format!(
+                    "The '{INJECT_EXECUTION_INFO_ATTR}' attribute is only supported on external \
+                     functions."
+                ),
+                stable_ptr: inject_execution_info_attr.stable_ptr().untyped(),
+            });
+        }
+    }
+    if let Some(deploy_helper_attr) = item_function.find_attr(db, DEPLOY_HELPER_ATTR) {
+        if entry_point_kind == EntryPointKind::Constructor {
+            data.deploy_function = Some(generate_deploy_function(db, item_function));
+        } else {
+            diagnostics.push(PluginDiagnostic {
+                message: format!(
+                    "The '{DEPLOY_HELPER_ATTR}' attribute is only supported on constructors."
+                ),
+                stable_ptr: deploy_helper_attr.stable_ptr().untyped(),
+            });
+        }
+    }
 
     let declaration = item_function.declaration(db);
+    let name = declaration.name(db);
+    // The wrapper of an entry point is named after it, and calls it through `super`.
+    if !validate_name_not_reserved(db, diagnostics, &name, "an entry point") {
+        return;
+    }
+    validate_selector_name_attr(db, diagnostics, item_function, entry_point_kind);
+    validate_view_external_attr(db, diagnostics, item_function, entry_point_kind);
+    let selector_name = if entry_point_kind == EntryPointKind::Constructor {
+        name.text(db)
+    } else {
+        entry_point_selector_name(db, item_function)
+    };
+    // The generated modules can't hold two functions with the same name, so only the first entry
+    // point with a name is generated.
+    if !add_entry_point_name(
+        data,
+        diagnostics,
+        name.text(db),
+        selector_name.clone(),
+        name.stable_ptr().untyped(),
+    ) {
+        return;
+    }
+    if selector_name != name.text(db) {
+        data.selector_names.insert(name.text(db), selector_name.clone());
+    }
+    // The selector is hashed once, and shared by everything generated for the entry point.
+    let selector = starknet_keccak(selector_name.as_bytes());
     if let OptionWrappedGenericParamList::WrappedGenericParamList(generic_params) =
         declaration.generic_params(db)
     {
@@ -421,6 +1247,10 @@ fn handle_entry_point(
         })
     }
 
+    if let Some(max_entry_point_params) = plugin.max_entry_point_params {
+        validate_param_count(db, diagnostics, item_function, max_entry_point_params);
+    }
+
     // TODO(ilya): Validate that an account contract has all the required functions.
 
     let mut declaration_node = RewriteNode::new_trimmed(declaration.as_syntax_node());
@@ -437,13 +1267,139 @@ fn handle_entry_point(
                 .set_str("".to_string());
         }
     }
-    data.abi_functions.push(RewriteNode::new_modified(vec![
+    let interface = get_interface(db, diagnostics, item_function);
+    let abi_function = RewriteNode::new_modified(vec![
         RewriteNode::Text(format!("#[{attr}]\n        ")),
-        declaration_node,
+        declaration_node.clone(),
         RewriteNode::Text(";\n        ".to_string()),
-    ]));
+    ]);
+    match &interface {
+        Some(interface) => {
+            data.interface_abis.entry(interface.clone()).or_default().functions.push(abi_function)
+        }
+        None => data.abi_functions.push(abi_function),
+    }
+
+    if entry_point_kind == EntryPointKind::External {
+        // Views get the contract state as a snapshot, unless they are marked as mutating.
+        let is_view = matches!(
+            params.elements(db).first().map(|param| param.type_clause(db).ty(db)),
+            Some(ast::Expr::Unary(_))
+        ) && !item_function.has_attr(db, VIEW_EXTERNAL_ATTR);
+        data.external_selectors.push((selector.clone(), is_view));
+    }
+    add_selector_name_check(
+        data,
+        diagnostics,
+        &selector_name,
+        &selector,
+        name.stable_ptr().untyped(),
+    );
+    add_selector_const(data, &name.text(db), &selector);
+    let calldata_struct = data.calldata_structs.as_mut().map(|calldata_structs| {
+        let struct_name = calldata_struct_name(&name.text(db));
+        calldata_structs.push(generate_calldata_struct(db, item_function, &struct_name));
+        struct_name
+    });
+
+    // When the ABI trait is implemented, the wrappers call the entry points through its impl.
+    let function_name = match &mut data.abi_impl_functions {
+        Some(abi_impl_functions) => {
+            let args = params
+                .elements(db)
+                .iter()
+                .map(|param| {
+                    let name = param.name(db).text(db);
+                    if is_ref_param(db, param) { format!("ref {name}") } else { name.to_string() }
+                })
+                .join(", ");
+            let abi_impl_function = RewriteNode::interpolate_patched(
+                &format!(
+                    "$declaration$ {{
+            $function_name$({args})
+        }}
+        "
+                ),
+                [
+                    ("declaration".to_string(), declaration_node),
+                    ("function_name".to_string(), function_name),
+                ]
+                .into(),
+            );
+            let abi_impl = match &interface {
+                Some(interface) => {
+                    let interface_abi = data.interface_abis.entry(interface.clone()).or_default();
+                    interface_abi.impl_functions.push(abi_impl_function);
+                    format!("{ABI_IMPL}_{interface}")
+                }
+                None => {
+                    abi_impl_functions.push(abi_impl_function);
+                    ABI_IMPL.to_string()
+                }
+            };
+            RewriteNode::interpolate_patched(
+                &format!("{abi_impl}::$function_name$"),
+                [(
+                    "function_name".to_string(),
+                    RewriteNode::new_trimmed(declaration.name(db).as_syntax_node()),
+                )]
+                .into(),
+            )
+        }
+        None => function_name,
+    };
+
+    if let (EntryPointKind::External, Some(generated_result_external_functions)) =
+        (entry_point_kind, &mut data.generated_result_external_functions)
+    {
+        // Diagnostics are reported once, by the generation of the panicking wrapper below.
+        if let Ok(generated_function) = generate_entry_point_wrapper(
+            db,
+            item_function,
+            function_name.clone(),
+            true,
+            None,
+            only_owner,
+            calldata_struct.as_deref(),
+            data.felt252_panics,
+            data.minimal_wrappers,
+        ) {
+            generated_result_external_functions.push(generated_function);
+            generated_result_external_functions.push(RewriteNode::Text("\n        ".to_string()));
+        }
+    }
+    if let (EntryPointKind::External, Some(generated_recording_external_functions)) =
+        (entry_point_kind, &mut data.generated_recording_external_functions)
+    {
+        // Diagnostics are reported once, by the generation of the panicking wrapper below.
+        if let Ok(generated_function) = generate_entry_point_wrapper(
+            db,
+            item_function,
+            function_name.clone(),
+            false,
+            Some(&selector),
+            only_owner,
+            calldata_struct.as_deref(),
+            data.felt252_panics,
+            data.minimal_wrappers,
+        ) {
+            generated_recording_external_functions.push(generated_function);
+            generated_recording_external_functions
+                .push(RewriteNode::Text("\n        ".to_string()));
+        }
+    }
 
-    match generate_entry_point_wrapper(db, item_function, function_name) {
+    match generate_entry_point_wrapper(
+        db,
+        item_function,
+        function_name,
+        false,
+        None,
+        only_owner,
+        calldata_struct.as_deref(),
+        data.felt252_panics,
+        data.minimal_wrappers,
+    ) {
         Ok(generated_function) => {
             let generated = match entry_point_kind {
                 EntryPointKind::Constructor => &mut data.generated_constructor_functions,
@@ -462,6 +1418,638 @@ fn handle_entry_point(
     }
 }
 
+/// Generates the DEPLOY_FUNCTION function for the given constructor. It takes the class hash and
+/// salt of the deployment followed by the constructor arguments, which are serialized with their
+/// `Serde` implementations into the calldata of the deployment.
+fn generate_deploy_function(
+    db: &dyn SyntaxGroup,
+    item_function: &ast::FunctionWithBody,
+) -> RewriteNode {
+    let mut params = vec![];
+    let mut serializations = vec![];
+    // Skipping the contract state parameter.
+    let constructor_params = item_function.declaration(db).signature(db).parameters(db);
+    for param in constructor_params.elements(db).iter().skip(1) {
+        let patches = [
+            ("name".to_string(), RewriteNode::new_trimmed(param.name(db).as_syntax_node())),
+            (
+                "type".to_string(),
+                RewriteNode::new_trimmed(param.type_clause(db).ty(db).as_syntax_node()),
+            ),
+        ];
+        params.push(RewriteNode::interpolate_patched(", $name$: $type$", patches.clone().into()));
+        serializations.push(RewriteNode::interpolate_patched(
+            &format!(
+                "
+    serde::Serde::<$type$>::serialize(@$name$, ref {CALLDATA_PARAM_NAME});"
+            ),
+            patches.into(),
+        ));
+    }
+    RewriteNode::interpolate_patched(
+        &formatdoc!(
+            "
+
+
+            fn {DEPLOY_FUNCTION}(
+                class_hash: starknet::ClassHash, contract_address_salt: felt252$params$
+            ) -> starknet::ContractAddress {{
+                let mut {CALLDATA_PARAM_NAME} = array::array_new();$serializations$
+                let (contract_address, _) = starknet::SyscallResultTrait::unwrap_syscall(
+                    starknet::syscalls::deploy_syscall(
+                        class_hash,
+                        contract_address_salt,
+                        array::ArrayTrait::span(@{CALLDATA_PARAM_NAME}),
+                        false,
+                    )
+                );
+                contract_address
+            }}"
+        ),
+        [
+            ("params".to_string(), RewriteNode::new_modified(params)),
+            ("serializations".to_string(), RewriteNode::new_modified(serializations)),
+        ]
+        .into(),
+    )
+}
+
+/// Adds the entry point `name`, whose selector is hashed from `selector_name`, to the entry points
+/// of the contract. Returns false, and reports a diagnostic at `stable_ptr`, if the contract
+/// already has an entry point with that name or selector.
+fn add_entry_point_name(
+    data: &mut ContractGenerationData,
+    diagnostics: &mut Vec<PluginDiagnostic>,
+    name: SmolStr,
+    selector_name: SmolStr,
+    stable_ptr: SyntaxStablePtrId,
+) -> bool {
+    let message = if !data.entry_point_names.insert(name.clone()) {
+        format!("An entry point named `{name}` is already defined in the contract.")
+    } else if !data.entry_point_selector_names.insert(selector_name.clone()) {
+        format!(
+            "An entry point with the selector name '{selector_name}' is already defined in the \
+             contract."
+        )
+    } else {
+        return true;
+    };
+    diagnostics.push(PluginDiagnostic { message, stable_ptr });
+    false
+}
+
+/// Validates the SELECTOR_NAME_ATTR attributes of the entry point. Constructors have a fixed
+/// selector, so they can't be annotated.
+fn validate_selector_name_attr(
+    db: &dyn SyntaxGroup,
+    diagnostics: &mut Vec<PluginDiagnostic>,
+    item_function: &ast::FunctionWithBody,
+    entry_point_kind: EntryPointKind,
+) {
+    let attrs = item_function.query_attr(db, SELECTOR_NAME_ATTR);
+    let Some(attr) = attrs.first() else { return; };
+    for duplicate_attr in attrs.iter().skip(1) {
+        diagnostics.push(PluginDiagnostic {
+            message: "An entry point may only have one selector name.".to_string(),
+            stable_ptr: duplicate_attr.stable_ptr().untyped(),
+        });
+    }
+    let message = if entry_point_kind == EntryPointKind::Constructor {
+        format!("The '{SELECTOR_NAME_ATTR}' attribute is not supported on constructors.")
+    } else if selector_name_from_attr(db, &attr.clone().structurize(db)).is_none() {
+        format!(
+            "`{SELECTOR_NAME_ATTR}` expects a single short string argument, which must be \
+             non-empty and consist of printable characters."
+        )
+    } else {
+        return;
+    };
+    diagnostics.push(PluginDiagnostic { message, stable_ptr: attr.stable_ptr().untyped() });
+}
+
+/// Validates the VIEW_EXTERNAL_ATTR attribute of the entry point, which is only supported on
+/// external functions taking `self` as a snapshot.
+fn validate_view_external_attr(
+    db: &dyn SyntaxGroup,
+    diagnostics: &mut Vec<PluginDiagnostic>,
+    item_function: &ast::FunctionWithBody,
+    entry_point_kind: EntryPointKind,
+) {
+    let Some(attr) = item_function.find_attr(db, VIEW_EXTERNAL_ATTR) else { return; };
+    let params = item_function.declaration(db).signature(db).parameters(db).elements(db);
+    let message = if entry_point_kind != EntryPointKind::External {
+        format!("The '{VIEW_EXTERNAL_ATTR}' attribute is only supported on external functions.")
+    } else if !matches!(
+        params.first().map(|param| param.type_clause(db).ty(db)),
+        Some(ast::Expr::Unary(_))
+    ) {
+        format!(
+            "The '{VIEW_EXTERNAL_ATTR}' attribute is only supported on functions taking `self` \
+             as a snapshot."
+        )
+    } else {
+        return;
+    };
+    diagnostics.push(PluginDiagnostic { message, stable_ptr: attr.stable_ptr().untyped() });
+}
+
+/// Adds the check of the SELECTOR_NAMES_FUNCTION function for the entry point `name`, whose
+/// selector is `selector`, if the function is generated. Names that don't fit in a short string are
+/// reported at `stable_ptr`.
+fn add_selector_name_check(
+    data: &mut ContractGenerationData,
+    diagnostics: &mut Vec<PluginDiagnostic>,
+    name: &str,
+    selector: &BigUint,
+    stable_ptr: SyntaxStablePtrId,
+) {
+    let Some(selector_name_checks) = &mut data.selector_name_checks else { return; };
+    if !validate_short_string_length(
+        diagnostics,
+        name,
+        "Entry point names",
+        SELECTOR_NAMES_ATTR,
+        stable_ptr,
+    ) {
+        return;
+    }
+    selector_name_checks.push(format!(
+        "
+    if selector == 0x{:x} {{
+        return '{}';
+    }}",
+        selector,
+        name.replace('\\', "\\\\").replace('\'', "\\'")
+    ));
+}
+
+/// Adds the `const` item of the SELECTORS_MODULE module for the entry point `name`, whose selector
+/// is `selector`, if the module is generated.
+fn add_selector_const(data: &mut ContractGenerationData, name: &str, selector: &BigUint) {
+    let Some(selector_consts) = &mut data.selector_consts else { return; };
+    selector_consts.push(format!(
+        "
+    const {}: felt252 = 0x{:x};",
+        name.to_uppercase(),
+        selector
+    ));
+}
+
+/// Returns the interface the entry point is grouped into by ENTRY_POINT_INTERFACE_ATTR, if any. The
+/// interface name must be a single identifier, and an entry point may only belong to one interface.
+fn get_interface(
+    db: &dyn SyntaxGroup,
+    diagnostics: &mut Vec<PluginDiagnostic>,
+    item_function: &ast::FunctionWithBody,
+) -> Option<SmolStr> {
+    let attrs = item_function.query_attr(db, ENTRY_POINT_INTERFACE_ATTR);
+    let attr = attrs.first()?;
+    for duplicate_attr in attrs.iter().skip(1) {
+        diagnostics.push(PluginDiagnostic {
+            message: "An entry point may only belong to one interface.".to_string(),
+            stable_ptr: duplicate_attr.stable_ptr().untyped(),
+        });
+    }
+    let name = match attr.arguments(db) {
+        OptionArgListParenthesized::ArgListParenthesized(args) => {
+            match &args.args(db).elements(db)[..] {
+                [arg] => Some(arg.as_syntax_node().get_text_without_trivia(db)),
+                _ => None,
+            }
+        }
+        OptionArgListParenthesized::Empty(_) => None,
+    };
+    match name {
+        Some(name) if is_identifier(&name) => Some(name.into()),
+        _ => {
+            diagnostics.push(PluginDiagnostic {
+                message: format!(
+                    "`{ENTRY_POINT_INTERFACE_ATTR}` expects a single interface name argument, \
+                     which must be an identifier."
+                ),
+                stable_ptr: attr.stable_ptr().untyped(),
+            });
+            None
+        }
+    }
+}
+
+/// Generates the ABI trait of the interface, named ABI_TRAIT followed by the interface name, and
+/// its impl if `has_abi_impl`, named ABI_IMPL followed by the interface name.
+fn generate_interface_abi(
+    interface: &str,
+    interface_abi: InterfaceAbi,
+    has_abi_impl: bool,
+) -> RewriteNode {
+    let abi_impl = if has_abi_impl {
+        RewriteNode::interpolate_patched(
+            &formatdoc!(
+                "
+
+                impl {ABI_IMPL}_{interface} of {ABI_TRAIT}_{interface}<ContractState> {{
+                    $impl_functions$
+                }}"
+            ),
+            [(
+                "impl_functions".to_string(),
+                RewriteNode::new_modified(interface_abi.impl_functions),
+            )]
+            .into(),
+        )
+    } else {
+        RewriteNode::Text("".to_string())
+    };
+    RewriteNode::interpolate_patched(
+        &formatdoc!(
+            "
+
+            trait {ABI_TRAIT}_{interface}<ContractState> {{
+                $functions$
+            }}$abi_impl$"
+        ),
+        [
+            ("functions".to_string(), RewriteNode::new_modified(interface_abi.functions)),
+            ("abi_impl".to_string(), abi_impl),
+        ]
+        .into(),
+    )
+}
+
+/// Returns the name of the calldata struct of the entry point `name`, e.g. `TransferFromCalldata`
+/// for `transfer_from`.
+fn calldata_struct_name(name: &str) -> String {
+    let camel_case_name: String = name
+        .split('_')
+        .map(|word| {
+            let mut chars = word.chars();
+            match chars.next() {
+                Some(first) => first.to_uppercase().chain(chars).collect(),
+                None => String::new(),
+            }
+        })
+        .collect();
+    format!("{camel_case_name}Calldata")
+}
+
+/// Generates the SUPPORTS_INTERFACE_FUNCTION function, returning true for the interface ids
+/// declared by the SUPPORTS_ATTR attributes of the contract. Each id must be a single felt252
+/// literal argument.
+fn generate_supports_interface_function(
+    db: &dyn SyntaxGroup,
+    diagnostics: &mut Vec<PluginDiagnostic>,
+    module_ast: &ast::ItemModule,
+) -> RewriteNode {
+    let mut interface_checks = vec![];
+    for attr in module_ast.query_attr(db, SUPPORTS_ATTR) {
+        let args = match attr.arguments(db) {
+            OptionArgListParenthesized::ArgListParenthesized(args) => args.args(db).elements(db),
+            OptionArgListParenthesized::Empty(_) => vec![],
+        };
+        let interface_id = match &args[..] {
+            [arg] => try_extract_felt252_literal(db, arg),
+            _ => None,
+        };
+        let Some(interface_id) = interface_id else {
+            diagnostics.push(PluginDiagnostic {
+                message: format!(
+                    "`{SUPPORTS_ATTR}` expects a single interface id argument, which must be a \
+                     felt252 literal."
+                ),
+                stable_ptr: attr.stable_ptr().untyped(),
+            });
+            continue;
+        };
+        interface_checks.push(format!(
+            "
+    if interface_id == 0x{interface_id:x} {{
+        return true;
+    }}"
+        ));
+    }
+    RewriteNode::Text(format!(
+        "
+
+#[{EXTERNAL_ATTR}(v0)]
+fn {SUPPORTS_INTERFACE_FUNCTION}(self: @ContractState, interface_id: felt252) -> bool {{{}
+    false
+}}",
+        interface_checks.join("")
+    ))
+}
+
+/// Adds the entry point of a view generated by the plugin, named `name`, taking the `params` names
+/// and types after `self`, and returning `return_type`. If `raw_output` is set, the view returns
+/// its serialized output itself, as with RAW_OUTPUT_ATTR. Diagnostics about the view are reported
+/// at `stable_ptr`.
+fn add_generated_view_entry_point(
+    data: &mut ContractGenerationData,
+    diagnostics: &mut Vec<PluginDiagnostic>,
+    stable_ptr: SyntaxStablePtrId,
+    name: &str,
+    params: &[(&str, &str)],
+    return_type: RewriteNode,
+    raw_output: bool,
+) {
+    add_generated_entry_point(
+        data,
+        diagnostics,
+        stable_ptr,
+        GeneratedEntryPoint {
+            name,
+            params,
+            return_type: Some(return_type),
+            is_view: true,
+            raw_output,
+            only_owner: false,
+        },
+    );
+}
+
+/// An external entry point generated by the plugin.
+struct GeneratedEntryPoint<'a> {
+    name: &'a str,
+    /// The names and types of the parameters after `self`.
+    params: &'a [(&'a str, &'a str)],
+    return_type: Option<RewriteNode>,
+    /// Whether `self` is a snapshot of the contract state, rather than a reference to it.
+    is_view: bool,
+    /// Whether the entry point returns its serialized output itself, as with RAW_OUTPUT_ATTR.
+    raw_output: bool,
+    /// Whether the wrapper fails unless the caller is the owner, as with ONLY_OWNER_ATTR.
+    only_owner: bool,
+}
+
+/// Adds the given entry point generated by the plugin. Diagnostics about the entry point are
+/// reported at `stable_ptr`.
+fn add_generated_entry_point(
+    data: &mut ContractGenerationData,
+    diagnostics: &mut Vec<PluginDiagnostic>,
+    stable_ptr: SyntaxStablePtrId,
+    entry_point: GeneratedEntryPoint<'_>,
+) {
+    let GeneratedEntryPoint { name, params, return_type, is_view, raw_output, only_owner } =
+        entry_point;
+    if !add_entry_point_name(data, diagnostics, name.into(), name.into(), stable_ptr) {
+        return;
+    }
+    let params_decl = params
+        .iter()
+        .map(|(param_name, param_type)| format!(", {param_name}: {param_type}"))
+        .join("");
+    let (self_param, self_arg, storage_arg) = if is_view {
+        ("self: @ContractState", "self", "@storage")
+    } else {
+        ("ref self: ContractState", "ref self", "ref storage")
+    };
+    let declaration = match &return_type {
+        Some(return_type) => RewriteNode::interpolate_patched(
+            &format!("fn {name}({self_param}{params_decl}) -> $return_type$"),
+            [("return_type".to_string(), return_type.clone())].into(),
+        ),
+        None => RewriteNode::Text(format!("fn {name}({self_param}{params_decl})")),
+    };
+    data.abi_functions.push(RewriteNode::new_modified(vec![
+        RewriteNode::Text(format!("#[{EXTERNAL_ATTR}]\n        ")),
+        declaration.clone(),
+        RewriteNode::Text(";\n        ".to_string()),
+    ]));
+    let selector = starknet_keccak(name.as_bytes());
+    data.external_selectors.push((selector.clone(), is_view));
+    add_selector_name_check(data, diagnostics, name, &selector, stable_ptr);
+    add_selector_const(data, name, &selector);
+
+    let args = params.iter().map(|(param_name, _)| format!(", {param_name}")).join("");
+    let wrapper_args =
+        params.iter().map(|(param_name, _)| format!(", {WRAPPER_ARG_PREFIX}{param_name}")).join("");
+    let arg_definitions = |result_output: bool| {
+        RewriteNode::new_modified(
+            params
+                .iter()
+                .map(|(param_name, param_type)| {
+                    let arg_name = format!("{WRAPPER_ARG_PREFIX}{param_name}");
+                    RewriteNode::interpolate_patched(
+                        &arg_definition_code(&arg_name, "", result_output),
+                        [("type_name".to_string(), RewriteNode::Text(param_type.to_string()))]
+                            .into(),
+                    )
+                })
+                .collect(),
+        )
+    };
+    let wrapped_function_name = match &mut data.abi_impl_functions {
+        Some(abi_impl_functions) => {
+            abi_impl_functions.push(RewriteNode::new_modified(vec![
+                declaration,
+                RewriteNode::Text(format!(
+                    " {{
+            {name}({self_arg}{args})
+        }}
+        "
+                )),
+            ]));
+            format!("{ABI_IMPL}::{name}")
+        }
+        None => name.to_string(),
+    };
+    let call = format!("super::{wrapped_function_name}({storage_arg}{wrapper_args})");
+    let output_handling = |result_output: bool| {
+        let output_handling = if raw_output {
+            if result_output {
+                RewriteNode::Text(format!("Result::Ok({call})"))
+            } else {
+                RewriteNode::Text(call.clone())
+            }
+        } else {
+            let output = if result_output {
+                "Result::Ok(array::ArrayTrait::span(@arr))"
+            } else {
+                "array::ArrayTrait::span(@arr)"
+            };
+            match &return_type {
+                Some(return_type) => RewriteNode::interpolate_patched(
+                    &format!(
+                        "let res = {call};
+            let mut arr = array::array_new();
+            serde::Serde::<$return_type$>::serialize(@res, ref arr);
+            {output}"
+                    ),
+                    [("return_type".to_string(), return_type.clone())].into(),
+                ),
+                None => RewriteNode::Text(format!(
+                    "{call};
+            let mut arr = array::array_new();
+            {output}"
+                )),
+            }
+        };
+        if only_owner { owner_check_code(output_handling, result_output) } else { output_handling }
+    };
+    let function_name = RewriteNode::Text(name.to_string());
+    let gas_check = !data.minimal_wrappers || !params.is_empty();
+    if let Some(generated_result_external_functions) = &mut data.generated_result_external_functions
+    {
+        generated_result_external_functions.push(generate_wrapper_code(
+            function_name.clone(),
+            arg_definitions(true),
+            output_handling(true),
+            RewriteNode::Text("".to_string()),
+            true,
+            gas_check,
+            data.felt252_panics,
+        ));
+        generated_result_external_functions.push(RewriteNode::Text("\n        ".to_string()));
+    }
+    data.generated_external_functions.push(generate_wrapper_code(
+        function_name,
+        arg_definitions(false),
+        output_handling(false),
+        RewriteNode::Text("".to_string()),
+        false,
+        gas_check,
+        data.felt252_panics,
+    ));
+    data.generated_external_functions.push(RewriteNode::Text("\n        ".to_string()));
+}
+
+/// Reports the item of the contract module named `name`, if any, as it would collide with the
+/// item of that name generated by the plugin, described by `generated_item`. Returns whether such
+/// an item was found.
+fn report_colliding_item(
+    db: &dyn SyntaxGroup,
+    diagnostics: &mut Vec<PluginDiagnostic>,
+    body: &ast::ModuleBody,
+    name: &str,
+    generated_item: &str,
+) -> bool {
+    let Some(item_name) = body
+        .items(db)
+        .elements(db)
+        .iter()
+        .filter_map(|item| item_name(db, item))
+        .find(|item_name| item_name.text(db) == name)
+    else {
+        return false;
+    };
+    diagnostics.push(PluginDiagnostic {
+        message: format!(
+            "The contract already declares an item named `{name}`, which would collide with \
+             {generated_item}."
+        ),
+        stable_ptr: item_name.stable_ptr().untyped(),
+    });
+    true
+}
+
+/// Returns the name of the given module item, or None for items that don't declare a name.
+fn item_name(db: &dyn SyntaxGroup, item: &ast::Item) -> Option<ast::TerminalIdentifier> {
+    Some(match item {
+        ast::Item::Constant(item) => item.name(db),
+        ast::Item::Module(item) => item.name(db),
+        ast::Item::FreeFunction(item) => item.declaration(db).name(db),
+        ast::Item::ExternFunction(item) => item.declaration(db).name(db),
+        ast::Item::ExternType(item) => item.name(db),
+        ast::Item::Trait(item) => item.name(db),
+        ast::Item::Impl(item) => item.name(db),
+        ast::Item::ImplAlias(item) => item.name(db),
+        ast::Item::Struct(item) => item.name(db),
+        ast::Item::Enum(item) => item.name(db),
+        ast::Item::TypeAlias(item) => item.name(db),
+        ast::Item::Use(_) | ast::Item::Missing(_) => return None,
+    })
+}
+
+/// Adds the constructor generated for contracts without one, and returns its code. It does
+/// nothing, and its wrapper fails with 'Input too long for arguments' if given calldata.
+fn add_default_constructor(data: &mut ContractGenerationData) -> RewriteNode {
+    let declaration = "fn constructor(ref self: ContractState)";
+    data.abi_functions.push(RewriteNode::Text(format!(
+        "#[{CONSTRUCTOR_ATTR}]\n        {declaration};\n        "
+    )));
+    let function_name = match &mut data.abi_impl_functions {
+        Some(abi_impl_functions) => {
+            abi_impl_functions.push(RewriteNode::Text(format!(
+                "{declaration} {{
+            constructor(ref self)
+        }}
+        "
+            )));
+            format!("{ABI_IMPL}::constructor")
+        }
+        None => "constructor".to_string(),
+    };
+    data.generated_constructor_functions.push(generate_wrapper_code(
+        RewriteNode::Text("constructor".to_string()),
+        RewriteNode::Text("".to_string()),
+        RewriteNode::Text(format!(
+            "super::{function_name}(ref storage);
+            let mut arr = array::array_new();
+            array::ArrayTrait::span(@arr)"
+        )),
+        RewriteNode::Text("".to_string()),
+        false,
+        // The constructor has no parameters.
+        !data.minimal_wrappers,
+        data.felt252_panics,
+    ));
+    data.generated_constructor_functions.push(RewriteNode::Text("\n        ".to_string()));
+    RewriteNode::Text(formatdoc!(
+        "
+
+        #[{CONSTRUCTOR_ATTR}]
+        {declaration} {{}}
+        "
+    ))
+}
+
+/// Validates the ONLY_OWNER_ATTR attribute of an UPGRADEABLE_ATTR contract, and returns whether
+/// the generated UPGRADE_FUNCTION entry point should be restricted to the owner.
+fn validate_contract_only_owner_attr(
+    db: &dyn SyntaxGroup,
+    diagnostics: &mut Vec<PluginDiagnostic>,
+    module_ast: &ast::ItemModule,
+    data: &mut ContractGenerationData,
+) -> bool {
+    let Some(attr) = module_ast.find_attr(db, ONLY_OWNER_ATTR) else {
+        return false;
+    };
+    validate_owner_member(diagnostics, data, attr.stable_ptr().untyped())
+}
+
+/// Validates that the contract has an OWNER_MEMBER_NAME storage member of type `ContractAddress`,
+/// as required by the ONLY_OWNER_ATTR attribute at `attr_ptr`. Returns whether it has one.
+fn validate_owner_member(
+    diagnostics: &mut Vec<PluginDiagnostic>,
+    data: &mut ContractGenerationData,
+    attr_ptr: SyntaxStablePtrId,
+) -> bool {
+    match &mut data.owner_member {
+        OwnerMember::Valid => true,
+        OwnerMember::Missing => {
+            diagnostics.push(PluginDiagnostic {
+                message: format!(
+                    "The '{ONLY_OWNER_ATTR}' attribute requires an '{OWNER_MEMBER_NAME}' storage \
+                     member."
+                ),
+                stable_ptr: attr_ptr,
+            });
+            false
+        }
+        OwnerMember::NotContractAddress { type_ptr, reported } => {
+            if !*reported {
+                diagnostics.push(PluginDiagnostic {
+                    message: format!(
+                        "The '{OWNER_MEMBER_NAME}' storage member must be a `ContractAddress` to \
+                         be used by the '{ONLY_OWNER_ATTR}' attribute."
+                    ),
+                    stable_ptr: *type_ptr,
+                });
+                *reported = true;
+            }
+            false
+        }
+    }
+}
+
 /// Validates the first parameter of an L1 handler is `from_address: felt252` or `_from_address:
 /// felt252`.
 fn validate_l1_handler_first_parameter(
@@ -3,6 +3,7 @@ use cairo_lang_defs::plugin::{
 };
 use cairo_lang_semantic::patcher::{PatchBuilder, RewriteNode};
 use cairo_lang_semantic::plugin::DynPluginAuxData;
+use cairo_lang_syntax::attribute::structured::AttributeStructurize;
 use cairo_lang_syntax::node::ast::{self, MaybeTraitBody, OptionReturnTypeClause};
 use cairo_lang_syntax::node::db::SyntaxGroup;
 use cairo_lang_syntax::node::helpers::QueryAttrs;
@@ -10,10 +11,10 @@ use cairo_lang_syntax::node::{Terminal, TypedSyntaxNode};
 use indoc::formatdoc;
 
 use super::aux_data::StarkNetABIAuxData;
-use super::consts::CALLDATA_PARAM_NAME;
+use super::consts::{CALLDATA_PARAM_NAME, SELECTOR_NAME_ATTR};
 use super::utils::is_ref_param;
 use super::{DEPRECATED_ABI_ATTR, INTERFACE_ATTR};
-use crate::contract::starknet_keccak;
+use crate::contract::{selector_name_from_attr, starknet_keccak};
 
 /// If the trait is annotated with ABI_ATTR, generate the relevant dispatcher logic.
 pub fn handle_trait(db: &dyn SyntaxGroup, trait_ast: ast::ItemTrait) -> PluginResult {
@@ -79,15 +80,26 @@ pub fn handle_trait(db: &dyn SyntaxGroup, trait_ast: ast::ItemTrait) -> PluginRe
                     });
                     skip_generation = true;
                 }
+                // Views get the contract state as a snapshot, and can't modify their arguments
+                // either.
+                let is_view = matches!(self_param.type_clause(db).ty(db), ast::Expr::Unary(_));
 
                 for param in params {
                     if is_ref_param(db, &param) {
                         skip_generation = true;
 
-                        diagnostics.push(PluginDiagnostic {
-                            message: "`ref` parameters are not supported in the ABI of a contract."
-                                .to_string(),
-                            stable_ptr: param.modifiers(db).stable_ptr().untyped(),
+                        diagnostics.push(if is_view {
+                            PluginDiagnostic {
+                                message: "View functions cannot have `ref` parameters.".to_string(),
+                                stable_ptr: param.stable_ptr().untyped(),
+                            }
+                        } else {
+                            PluginDiagnostic {
+                                message: "`ref` parameters are not supported in the ABI of a \
+                                          contract."
+                                    .to_string(),
+                                stable_ptr: param.modifiers(db).stable_ptr().untyped(),
+                            }
                         })
                     }
 
@@ -101,16 +113,21 @@ pub fn handle_trait(db: &dyn SyntaxGroup, trait_ast: ast::ItemTrait) -> PluginRe
                     }
 
                     let param_type = param.type_clause(db).ty(db);
-                    let type_name = &param_type.as_syntax_node().get_text(db);
                     serialization_code.push(RewriteNode::interpolate_patched(
                         &formatdoc!(
-                            "        serde::Serde::<{type_name}>::serialize(@$arg_name$, ref \
+                            "        serde::Serde::<$type_name$>::serialize(@$arg_name$, ref \
                              {CALLDATA_PARAM_NAME});\n"
                         ),
-                        [(
-                            "arg_name".to_string(),
-                            RewriteNode::new_trimmed(param.name(db).as_syntax_node()),
-                        )]
+                        [
+                            (
+                                "arg_name".to_string(),
+                                RewriteNode::new_trimmed(param.name(db).as_syntax_node()),
+                            ),
+                            (
+                                "type_name".to_string(),
+                                RewriteNode::new_trimmed(param_type.as_syntax_node()),
+                            ),
+                        ]
                         .into(),
                     ));
                 }
@@ -122,16 +139,19 @@ pub fn handle_trait(db: &dyn SyntaxGroup, trait_ast: ast::ItemTrait) -> PluginRe
                 }
 
                 let ret_decode = match signature.ret_ty(db) {
-                    OptionReturnTypeClause::Empty(_) => "".to_string(),
+                    OptionReturnTypeClause::Empty(_) => RewriteNode::Text("".to_string()),
                     OptionReturnTypeClause::ReturnTypeClause(ty) => {
-                        let ret_type_ast = ty.ty(db);
-                        let type_name = ret_type_ast.as_syntax_node().get_text(db);
-                        format!(
+                        RewriteNode::interpolate_patched(
                             "
         option::OptionTrait::expect(
-            serde::Serde::<{type_name}>::deserialize(ref ret_data),
+            serde::Serde::<$type_name$>::deserialize(ref ret_data),
             'Returned data too short',
-        )"
+        )",
+                            [(
+                                "type_name".to_string(),
+                                RewriteNode::new_trimmed(ty.ty(db).as_syntax_node()),
+                            )]
+                            .into(),
                         )
                     }
                 };
@@ -139,9 +159,22 @@ pub fn handle_trait(db: &dyn SyntaxGroup, trait_ast: ast::ItemTrait) -> PluginRe
                     "$func_decl$;",
                     [("func_decl".to_string(), dispatcher_signature(db, &declaration, "T"))].into(),
                 ));
+                let mut selector_name = declaration.name(db).text(db);
+                if let Some(attr) = func.find_attr(db, SELECTOR_NAME_ATTR) {
+                    match selector_name_from_attr(db, &attr.clone().structurize(db)) {
+                        Some(name) => selector_name = name,
+                        None => diagnostics.push(PluginDiagnostic {
+                            message: format!(
+                                "`{SELECTOR_NAME_ATTR}` expects a single short string argument, \
+                                 which must be non-empty and consist of printable characters."
+                            ),
+                            stable_ptr: attr.stable_ptr().untyped(),
+                        }),
+                    }
+                }
                 let entry_point_selector = RewriteNode::Text(format!(
                     "0x{:x}",
-                    starknet_keccak(declaration.name(db).text(db).as_bytes())
+                    starknet_keccak(selector_name.as_bytes())
                 ));
                 contract_caller_method_impls.push(declaration_method_impl(
                     dispatcher_signature(db, &declaration, &contract_caller_name),
@@ -225,7 +258,7 @@ fn declaration_method_impl(
     member: &str,
     syscall: &str,
     serialization_code: Vec<RewriteNode>,
-    ret_decode: String,
+    ret_decode: RewriteNode,
 ) -> RewriteNode {
     RewriteNode::interpolate_patched(
         &formatdoc!(
@@ -249,7 +282,7 @@ fn declaration_method_impl(
             ("syscall".to_string(), RewriteNode::Text(syscall.to_string())),
             ("member".to_string(), RewriteNode::Text(member.to_string())),
             ("serialization_code".to_string(), RewriteNode::new_modified(serialization_code)),
-            ("deserialization_code".to_string(), RewriteNode::Text(ret_decode)),
+            ("deserialization_code".to_string(), ret_decode),
         ]
         .into(),
     )
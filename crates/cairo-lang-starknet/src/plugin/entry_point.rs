@@ -1,17 +1,28 @@
 use cairo_lang_defs::plugin::PluginDiagnostic;
 use cairo_lang_semantic::patcher::RewriteNode;
+use cairo_lang_syntax::attribute::structured::AttributeStructurize;
 use cairo_lang_syntax::node::ast::{
-    self, Attribute, FunctionWithBody, OptionArgListParenthesized, OptionReturnTypeClause,
+    self, Attribute, FunctionWithBody, OptionArgListParenthesized, OptionImplicitsClause,
+    OptionReturnTypeClause,
 };
 use cairo_lang_syntax::node::db::SyntaxGroup;
 use cairo_lang_syntax::node::helpers::QueryAttrs;
 use cairo_lang_syntax::node::{Terminal, TypedSyntaxNode};
 use itertools::Itertools;
+use num_bigint::BigUint;
+use smol_str::SmolStr;
 
 use super::consts::{
-    CONSTRUCTOR_ATTR, EXTERNAL_ATTR, IMPLICIT_PRECEDENCE, L1_HANDLER_ATTR, RAW_OUTPUT_ATTR,
+    CALLDATA_MODULE, CONSTRUCTOR_ATTR, EXTERNAL_ATTR, IMPLICIT_PRECEDENCE,
+    INJECT_EXECUTION_INFO_ATTR, KEEP_GAS_CHECK_ATTR, L1_HANDLER_ATTR, MAX_ARRAY_NESTING_DEPTH,
+    OWNER_MEMBER_NAME, RAW_OUTPUT_ATTR, RECORDED_ARGS_NAME, REST_ARGS_ATTR, RETURNS_OPTIONAL_ATTR,
+    SELECTOR_NAME_ATTR, STORAGE_MODULE, STRICT_ARGS_ATTR, WRAPPER_ARG_PREFIX,
 };
-use super::utils::{is_felt252_span, is_ref_param};
+use super::utils::{
+    array_nesting_depth, is_execution_info, is_felt252, is_felt252_array, is_felt252_span,
+    is_ref_param, validate_short_string_length,
+};
+use crate::contract::selector_name_from_attr;
 
 /// Kind of an entry point. Determined by the entry point's attributes.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -27,8 +38,7 @@ impl EntryPointKind {
         diagnostics: &mut Vec<PluginDiagnostic>,
         item_function: &FunctionWithBody,
     ) -> Option<Self> {
-        if has_external_attribute(db, diagnostics, &ast::Item::FreeFunction(item_function.clone()))
-        {
+        if has_external_attribute(db, diagnostics, item_function) {
             Some(EntryPointKind::External)
         } else if item_function.has_attr(db, CONSTRUCTOR_ATTR) {
             Some(EntryPointKind::Constructor)
@@ -49,11 +59,54 @@ impl EntryPointKind {
     }
 }
 
+/// Returns the string hashed into the selector of the entry point, which is given by
+/// SELECTOR_NAME_ATTR and defaults to the name of the entry point.
+pub fn entry_point_selector_name(db: &dyn SyntaxGroup, function: &FunctionWithBody) -> SmolStr {
+    function
+        .find_attr(db, SELECTOR_NAME_ATTR)
+        .and_then(|attr| selector_name_from_attr(db, &attr.structurize(db)))
+        .unwrap_or_else(|| function.declaration(db).name(db).text(db))
+}
+
+/// The diagnostic of entry points whose first parameter isn't the contract state, e.g. when ported
+/// from the older syntax of free functions accessing storage directly.
+const MISSING_SELF_PARAM_MESSAGE: &str = "Entry points must take the contract state as their \
+                                          first parameter, either as `self: @ContractState` for \
+                                          views or as `ref self: ContractState`.";
+
 /// Generates Cairo code for an entry point wrapper.
+/// If `result_output` is true, the wrapper returns `Result::<Span::<felt252>, Array::<felt252>>`
+/// and the deserialization and gas failures are returned as `Err` instead of panicking.
+/// If `record_args_selector` is given, the wrapper emits the reserialized arguments as an event
+/// keyed by it, which is the selector of the entry point, before calling it, so tests can inspect
+/// the decoded arguments.
+/// If the function is marked with REST_ARGS_ATTR, its last parameter is filled with all the
+/// remaining calldata instead of being deserialized.
+/// If `calldata_struct` is given, the arguments are deserialized at once as the struct of that name
+/// in CALLDATA_MODULE, see [generate_calldata_struct].
+/// If `only_owner` is true, the wrapper fails unless the caller is the address in the
+/// OWNER_MEMBER_NAME storage member.
+/// If the function is marked with RETURNS_OPTIONAL_ATTR, its `felt252` return value is serialized
+/// as an `Option::<felt252>`, which is `None` if the value is zero.
+/// If the function is marked with STRICT_ARGS_ATTR, the length of the remaining calldata is
+/// checked after each argument followed only by arguments of a known serialized size.
+/// If the function is marked with INJECT_EXECUTION_INFO_ATTR, its first parameter after `self` is
+/// bound to the result of `get_execution_info_syscall` instead of being deserialized.
+/// If `minimal_wrappers` is true and the function takes no arguments besides `self`, the gas check
+/// before the (empty) deserialization of the arguments is omitted, unless the function is marked
+/// with KEEP_GAS_CHECK_ATTR.
+/// See [generate_wrapper_code] for `felt252_panics`.
+#[allow(clippy::too_many_arguments)]
 pub fn generate_entry_point_wrapper(
     db: &dyn SyntaxGroup,
     function: &FunctionWithBody,
     wrapped_function_name: RewriteNode,
+    result_output: bool,
+    record_args_selector: Option<&BigUint>,
+    only_owner: bool,
+    calldata_struct: Option<&str>,
+    felt252_panics: bool,
+    minimal_wrappers: bool,
 ) -> Result<RewriteNode, Vec<PluginDiagnostic>> {
     let declaration = function.declaration(db);
     let sig = declaration.signature(db);
@@ -62,55 +115,206 @@ pub fn generate_entry_point_wrapper(
     let mut arg_names = Vec::new();
     let mut arg_definitions = Vec::new();
     let mut ref_appends = Vec::new();
+    let mut record_appends = Vec::new();
+    let mut calldata_struct_members = Vec::new();
 
     let raw_output = function.has_attr(db, RAW_OUTPUT_ATTR);
+    let rest_args = function.has_attr(db, REST_ARGS_ATTR);
+    let returns_optional = function.has_attr(db, RETURNS_OPTIONAL_ATTR);
+    let strict_args = function.has_attr(db, STRICT_ARGS_ATTR);
 
     let Some(first_param) = params.next() else {
-        return Err(vec![PluginDiagnostic{
-            message: format!("`{RAW_OUTPUT_ATTR}` functions must get a 'self' param."),
+        return Err(vec![PluginDiagnostic {
+            message: MISSING_SELF_PARAM_MESSAGE.to_string(),
             stable_ptr: sig.stable_ptr().untyped(),
         }]);
     };
     if first_param.name(db).text(db) != "self" {
         return Err(vec![PluginDiagnostic {
-            message: format!("`{RAW_OUTPUT_ATTR}` functions must get a 'self' param."),
-            stable_ptr: sig.stable_ptr().untyped(),
+            message: MISSING_SELF_PARAM_MESSAGE.to_string(),
+            stable_ptr: first_param.name(db).stable_ptr().untyped(),
         }]);
     };
     let is_snapshot = matches!(first_param.type_clause(db).ty(db), ast::Expr::Unary(_));
     // TODO(spapini): Check modifiers and type.
 
-    let input_data_short_err = "'Input too short for arguments'";
-    for param in params {
-        let arg_name = format!("__arg_{}", param.name(db).text(db));
-        let arg_type_ast = param.type_clause(db).ty(db);
-        let type_name = arg_type_ast.as_syntax_node().get_text_without_trivia(db);
+    let implicits = supported_implicits(db, &mut diagnostics, &sig);
+    let implicits_clause = if implicits.is_empty() {
+        RewriteNode::Text("".to_string())
+    } else {
+        RewriteNode::interpolate_patched(
+            " implicits($implicits$)",
+            [(
+                "implicits".to_string(),
+                RewriteNode::new_modified(
+                    Itertools::intersperse(
+                        implicits
+                            .iter()
+                            .map(|implicit| RewriteNode::new_trimmed(implicit.as_syntax_node())),
+                        RewriteNode::Text(", ".to_string()),
+                    )
+                    .collect(),
+                ),
+            )]
+            .into(),
+        )
+    };
+
+    let mut params = params.collect_vec();
+    let injected_param = if function.has_attr(db, INJECT_EXECUTION_INFO_ATTR) {
+        take_execution_info_param(db, &mut diagnostics, &sig, &mut params)
+    } else {
+        None
+    };
+    let has_rest_param =
+        matches!(params.last(), Some(param) if is_felt252_array(db, &param.type_clause(db).ty(db)));
+    if rest_args && !has_rest_param {
+        diagnostics.push(PluginDiagnostic {
+            message: format!(
+                "`{REST_ARGS_ATTR}` functions must have a trailing `Array::<felt252>` parameter."
+            ),
+            stable_ptr: sig.parameters(db).stable_ptr().untyped(),
+        });
+    }
+
+    // The number of felts the arguments following each argument are serialized into, if known.
+    let mut following_sizes = vec![Some(0); params.len()];
+    for i in (1..params.len()).rev() {
+        let ty = params[i].type_clause(db).ty(db);
+        let ty = try_extract_snapshot_inner_type(db, &ty).unwrap_or(ty);
+        let size = if rest_args && i + 1 == params.len() { None } else { serialized_size(db, &ty) };
+        following_sizes[i - 1] = following_sizes[i].zip(size).map(|(a, b)| a + b);
+    }
+
+    for (i, param) in params.iter().enumerate() {
+        let is_rest_param = rest_args && i + 1 == params.len();
+        let arg_name = format!("{WRAPPER_ARG_PREFIX}{}", param.name(db).text(db));
+        let mut arg_type_ast = param.type_clause(db).ty(db);
+        // Snapshot parameters are deserialized as their inner type, and passed as a snapshot.
+        let snapshot_inner_type = try_extract_snapshot_inner_type(db, &arg_type_ast);
+        let is_snapshot_param = snapshot_inner_type.is_some();
+        if let Some(inner_type_ast) = snapshot_inner_type {
+            arg_type_ast = inner_type_ast;
+        }
+        let type_name = RewriteNode::new_trimmed(arg_type_ast.as_syntax_node());
+        validate_array_nesting_depth(db, &mut diagnostics, &arg_type_ast);
 
-        let is_ref = is_ref_param(db, &param);
+        let is_ref = is_ref_param(db, param);
         if raw_output && is_ref {
             diagnostics.push(PluginDiagnostic {
                 message: format!("`{RAW_OUTPUT_ATTR}` functions cannot have `ref` parameters."),
                 stable_ptr: param.modifiers(db).stable_ptr().untyped(),
             });
         }
+        if is_snapshot_param && is_ref {
+            diagnostics.push(PluginDiagnostic {
+                message: "Snapshot parameters cannot be `ref`.".to_string(),
+                stable_ptr: param.modifiers(db).stable_ptr().untyped(),
+            });
+        }
 
-        let ref_modifier = if is_ref { "ref " } else { "" };
-        arg_names.push(format!("{ref_modifier}{arg_name}"));
+        let arg_modifier = if is_ref {
+            "ref "
+        } else if is_snapshot_param {
+            "@"
+        } else {
+            ""
+        };
+        arg_names.push(format!("{arg_modifier}{arg_name}"));
         let mut_modifier = if is_ref { "mut " } else { "" };
-        // TODO(yuval): use panicable version of deserializations when supported.
-        let arg_definition = format!(
-            "
-            let {mut_modifier}{arg_name} =
-                serde::Serde::<{type_name}>::deserialize(ref data).expect({input_data_short_err});"
-        );
-        arg_definitions.push(arg_definition);
+        let arg_definition = if calldata_struct.is_some() && !is_rest_param {
+            calldata_struct_members
+                .push(format!("{}: {mut_modifier}{arg_name}", param.name(db).text(db)));
+            None
+        } else if is_rest_param {
+            // The `withdraw_gas` of the loop is added by the compiler, like in any loop.
+            Some(format!(
+                "
+            let mut {arg_name} = array::array_new();
+            loop {{
+                match array::SpanTrait::pop_front(ref data) {{
+                    Option::Some(value) => {{
+                        array::array_append(ref {arg_name}, *value);
+                    }},
+                    Option::None(()) => {{
+                        break ();
+                    }},
+                }};
+            }};"
+            ))
+        } else {
+            Some(arg_definition_code(&arg_name, mut_modifier, result_output))
+        };
+        if let Some(arg_definition) = arg_definition {
+            if !arg_definitions.is_empty() {
+                arg_definitions.push(RewriteNode::Text("\n".to_string()));
+            }
+            arg_definitions.push(RewriteNode::interpolate_patched(
+                &arg_definition,
+                [("type_name".to_string(), type_name.clone())].into(),
+            ));
+            // The length after the last argument is checked for all entry points.
+            if strict_args && i + 1 < params.len() {
+                if let Some(following_size) = following_sizes[i] {
+                    let param_name = param.name(db);
+                    if validate_short_string_length(
+                        &mut diagnostics,
+                        &param_name.text(db),
+                        "Parameter names",
+                        STRICT_ARGS_ATTR,
+                        param_name.stable_ptr().untyped(),
+                    ) {
+                        arg_definitions.push(RewriteNode::Text(remaining_length_check_code(
+                            &param_name.text(db),
+                            following_size,
+                            result_output,
+                        )));
+                    }
+                }
+            }
+        }
 
+        if record_args_selector.is_some() {
+            record_appends.push(RewriteNode::interpolate_patched(
+                &format!(
+                    "\n            serde::Serde::<$type_name$>::serialize(@{arg_name}, ref \
+                     {RECORDED_ARGS_NAME});"
+                ),
+                [("type_name".to_string(), type_name.clone())].into(),
+            ));
+        }
         if is_ref {
-            ref_appends.push(RewriteNode::Text(format!(
-                "\n            serde::Serde::<{type_name}>::serialize(@{arg_name}, ref arr);"
-            )));
+            ref_appends.push(RewriteNode::interpolate_patched(
+                &format!(
+                    "\n            serde::Serde::<$type_name$>::serialize(@{arg_name}, ref arr);"
+                ),
+                [("type_name".to_string(), type_name)].into(),
+            ));
         }
     }
+    if let Some(injected_param) = &injected_param {
+        arg_names.insert(0, format!("{WRAPPER_ARG_PREFIX}{}", injected_param.name(db).text(db)));
+    }
+    if let Some(calldata_struct) = calldata_struct {
+        // The struct is deserialized first, as a trailing rest argument consumes the remaining
+        // calldata.
+        let struct_path = format!("super::{CALLDATA_MODULE}::{calldata_struct}");
+        let struct_definition = format!(
+            "{}
+            let {struct_path} {{ {} }} = calldata;",
+            arg_definition_code("calldata", "", result_output),
+            calldata_struct_members.join(", ")
+        );
+        let mut definitions = vec![RewriteNode::interpolate_patched(
+            &struct_definition,
+            [("type_name".to_string(), RewriteNode::Text(struct_path))].into(),
+        )];
+        if !arg_definitions.is_empty() {
+            definitions.push(RewriteNode::Text("\n".to_string()));
+        }
+        definitions.extend(arg_definitions);
+        arg_definitions = definitions;
+    }
     let arg_names_str = arg_names.join(", ");
 
     let function_name = RewriteNode::new_trimmed(declaration.name(db).as_syntax_node());
@@ -122,16 +326,47 @@ pub fn generate_entry_point_wrapper(
     let ret_ty = sig.ret_ty(db);
     let (let_res, append_res, return_ty_is_felt252_span, ret_type_ptr) = match &ret_ty {
         OptionReturnTypeClause::Empty(type_clause_ast) => {
-            ("", "".to_string(), false, type_clause_ast.stable_ptr().untyped())
+            if returns_optional {
+                diagnostics.push(PluginDiagnostic {
+                    message: format!("`{RETURNS_OPTIONAL_ATTR}` functions must return `felt252`."),
+                    stable_ptr: sig.stable_ptr().untyped(),
+                });
+            }
+            ("", RewriteNode::Text("".to_string()), false, type_clause_ast.stable_ptr().untyped())
         }
         OptionReturnTypeClause::ReturnTypeClause(ty) => {
             let ret_type_ast = ty.ty(db);
+            validate_array_nesting_depth(db, &mut diagnostics, &ret_type_ast);
 
             let return_ty_is_felt252_span = is_felt252_span(db, &ret_type_ast);
-            let ret_type_name = ret_type_ast.as_syntax_node().get_text_without_trivia(db);
+            let append_res = if returns_optional {
+                if !is_felt252(db, &ret_type_ast) {
+                    diagnostics.push(PluginDiagnostic {
+                        message: format!(
+                            "`{RETURNS_OPTIONAL_ATTR}` functions must return `felt252`."
+                        ),
+                        stable_ptr: ret_type_ast.stable_ptr().untyped(),
+                    });
+                }
+                RewriteNode::Text(
+                    "
+            let res = if res == 0 { Option::None(()) } else { Option::Some(res) };
+            serde::Serde::<Option::<felt252>>::serialize(@res, ref arr);"
+                        .to_string(),
+                )
+            } else {
+                RewriteNode::interpolate_patched(
+                    "\n            serde::Serde::<$ret_type_name$>::serialize(@res, ref arr);",
+                    [(
+                        "ret_type_name".to_string(),
+                        RewriteNode::new_trimmed(ret_type_ast.as_syntax_node()),
+                    )]
+                    .into(),
+                )
+            };
             (
                 "\n            let res = ",
-                format!("\n            serde::Serde::<{ret_type_name}>::serialize(@res, ref arr);"),
+                append_res,
                 return_ty_is_felt252_span,
                 ret_type_ast.stable_ptr().untyped(),
             )
@@ -150,16 +385,23 @@ pub fn generate_entry_point_wrapper(
     }
 
     let storage_arg = if is_snapshot { "@storage" } else { "ref storage" };
-    let output_handling_string = if raw_output {
-        format!("$wrapped_name$({storage_arg}, {arg_names_str})")
-    } else {
-        format!(
-            "{let_res}$wrapped_name$({storage_arg}, {arg_names_str});
+    let output_handling_string = match (raw_output, result_output) {
+        (true, false) => format!("$wrapped_name$({storage_arg}, {arg_names_str})"),
+        (true, true) => format!("Result::Ok($wrapped_name$({storage_arg}, {arg_names_str}))"),
+        (false, _) => {
+            let output = if result_output {
+                "Result::Ok(array::ArrayTrait::span(@arr))"
+            } else {
+                "array::ArrayTrait::span(@arr)"
+            };
+            format!(
+                "{let_res}$wrapped_name$({storage_arg}, {arg_names_str});
             let mut arr = array::array_new();
             // References.$ref_appends$
-            // Result.{append_res}
-            array::ArrayTrait::span(@arr)"
-        )
+            // Result.$append_res$
+            {output}"
+            )
+        }
     };
 
     let output_handling = RewriteNode::interpolate_patched(
@@ -167,49 +409,458 @@ pub fn generate_entry_point_wrapper(
         [
             ("wrapped_name".to_string(), wrapped_name),
             ("ref_appends".to_string(), RewriteNode::new_modified(ref_appends)),
+            ("append_res".to_string(), append_res),
         ]
         .into(),
     );
+    let output_handling = match &injected_param {
+        Some(injected_param) => RewriteNode::interpolate_patched(
+            &format!(
+                "let {WRAPPER_ARG_PREFIX}{} = box::BoxTrait::unbox(
+                starknet::SyscallResultTrait::unwrap_syscall(
+                    starknet::syscalls::get_execution_info_syscall()
+                )
+            );
+            $output_handling$",
+                injected_param.name(db).text(db)
+            ),
+            [("output_handling".to_string(), output_handling)].into(),
+        ),
+        None => output_handling,
+    };
+    let output_handling = if let Some(selector) = record_args_selector {
+        RewriteNode::interpolate_patched(
+            &format!(
+                "let mut {RECORDED_ARGS_NAME} = array::array_new();$record_appends$
+            let mut keys = array::array_new();
+            array::array_append(ref keys, 0x{selector:x});
+            starknet::SyscallResultTrait::unwrap_syscall(
+                starknet::syscalls::emit_event_syscall(
+                    array::ArrayTrait::span(@keys),
+                    array::ArrayTrait::span(@{RECORDED_ARGS_NAME}),
+                )
+            );
+            $output_handling$"
+            ),
+            [
+                ("record_appends".to_string(), RewriteNode::new_modified(record_appends)),
+                ("output_handling".to_string(), output_handling),
+            ]
+            .into(),
+        )
+    } else {
+        output_handling
+    };
+    let output_handling =
+        if only_owner { owner_check_code(output_handling, result_output) } else { output_handling };
+
+    // Nothing can run out of gas before the final gas check if there are no arguments, and the
+    // function itself is paid for by the final gas check.
+    let gas_check =
+        !minimal_wrappers || !params.is_empty() || function.has_attr(db, KEEP_GAS_CHECK_ATTR);
+    Ok(generate_wrapper_code(
+        function_name,
+        RewriteNode::new_modified(arg_definitions),
+        output_handling,
+        implicits_clause,
+        result_output,
+        gas_check,
+        felt252_panics,
+    ))
+}
 
+/// Removes the parameter of an INJECT_EXECUTION_INFO_ATTR function receiving the execution info,
+/// its first parameter after `self`, from `params` and returns it. Reports it if it's missing or
+/// isn't an `ExecutionInfo`.
+fn take_execution_info_param(
+    db: &dyn SyntaxGroup,
+    diagnostics: &mut Vec<PluginDiagnostic>,
+    sig: &ast::FunctionSignature,
+    params: &mut Vec<ast::Param>,
+) -> Option<ast::Param> {
+    let message = format!(
+        "`{INJECT_EXECUTION_INFO_ATTR}` functions must take a `starknet::info::ExecutionInfo` as \
+         their first parameter after `self`."
+    );
+    if params.is_empty() {
+        diagnostics.push(PluginDiagnostic {
+            message,
+            stable_ptr: sig.parameters(db).stable_ptr().untyped(),
+        });
+        return None;
+    }
+    let param = params.remove(0);
+    let type_ast = param.type_clause(db).ty(db);
+    if !is_execution_info(db, &type_ast) {
+        diagnostics.push(PluginDiagnostic { message, stable_ptr: type_ast.stable_ptr().untyped() });
+    } else if !param.modifiers(db).elements(db).is_empty() {
+        diagnostics.push(PluginDiagnostic {
+            message: format!(
+                "The execution info parameter of `{INJECT_EXECUTION_INFO_ATTR}` functions cannot \
+                 have modifiers."
+            ),
+            stable_ptr: param.modifiers(db).stable_ptr().untyped(),
+        });
+    }
+    Some(param)
+}
+
+/// Prefixes the `output_handling` code of an entry point wrapper with a check that the caller is
+/// the address in the OWNER_MEMBER_NAME storage member. See [generate_wrapper_code] for
+/// `result_output`.
+pub fn owner_check_code(output_handling: RewriteNode, result_output: bool) -> RewriteNode {
+    let not_owner_code = if result_output {
+        return_err_code("'Not owner'", "                ")
+    } else {
+        "\n                panic_with_felt252('Not owner');".to_string()
+    };
+    RewriteNode::interpolate_patched(
+        &format!(
+            "let owner =
+                super::{STORAGE_MODULE}::{OWNER_MEMBER_NAME}::InternalContractStateTrait::read(
+                    @storage.{OWNER_MEMBER_NAME}
+                );
+            if starknet::contract_address_to_felt252(starknet::get_caller_address())
+                != starknet::contract_address_to_felt252(owner) {{{not_owner_code}
+            }}
+            $output_handling$"
+        ),
+        [("output_handling".to_string(), output_handling)].into(),
+    )
+}
+
+/// Generates the calldata struct of the entry point `function`, named `struct_name`, with a member
+/// per argument, and its `Serde` implementation. The implementation encodes the arguments as the
+/// entry point wrapper decodes them, so it may be used to build the calldata of calls to the entry
+/// point. The trailing parameter of REST_ARGS_ATTR functions is not a member, as it is not
+/// deserialized.
+pub fn generate_calldata_struct(
+    db: &dyn SyntaxGroup,
+    function: &FunctionWithBody,
+    struct_name: &str,
+) -> RewriteNode {
+    // Skipping the contract state parameter.
+    let params = function.declaration(db).signature(db).parameters(db).elements(db);
+    let mut params = params.into_iter().skip(1).collect_vec();
+    // The injected execution info is not part of the calldata.
+    if function.has_attr(db, INJECT_EXECUTION_INFO_ATTR) && !params.is_empty() {
+        params.remove(0);
+    }
+    let has_rest_param =
+        matches!(params.last(), Some(param) if is_felt252_array(db, &param.type_clause(db).ty(db)));
+    if function.has_attr(db, REST_ARGS_ATTR) && has_rest_param {
+        params.pop();
+    }
+
+    let mut members = vec![];
+    let mut serializations = vec![];
+    let mut deserializations = vec![];
+    for param in params {
+        let mut type_ast = param.type_clause(db).ty(db);
+        if let Some(inner_type_ast) = try_extract_snapshot_inner_type(db, &type_ast) {
+            type_ast = inner_type_ast;
+        }
+        let patches = [
+            ("name".to_string(), RewriteNode::new_trimmed(param.name(db).as_syntax_node())),
+            ("type".to_string(), RewriteNode::new_trimmed(type_ast.as_syntax_node())),
+        ];
+        members.push(RewriteNode::interpolate_patched(
+            "\n        $name$: $type$,",
+            patches.clone().into(),
+        ));
+        serializations.push(RewriteNode::interpolate_patched(
+            "\n            serde::Serde::<$type$>::serialize(self.$name$, ref output);",
+            patches.clone().into(),
+        ));
+        deserializations.push(RewriteNode::interpolate_patched(
+            "\n                $name$: serde::Serde::<$type$>::deserialize(ref serialized)?,",
+            patches.into(),
+        ));
+    }
+
+    RewriteNode::interpolate_patched(
+        &format!(
+            "
+    #[derive(Drop)]
+    struct {struct_name} {{$members$
+    }}
+    impl {struct_name}Serde of serde::Serde::<{struct_name}> {{
+        fn serialize(self: @{struct_name}, ref output: array::Array<felt252>) {{$serializations$
+        }}
+        fn deserialize(ref serialized: array::Span<felt252>) -> Option<{struct_name}> {{
+            Option::Some({struct_name} {{$deserializations$
+            }})
+        }}
+    }}"
+        ),
+        [
+            ("members".to_string(), RewriteNode::new_modified(members)),
+            ("serializations".to_string(), RewriteNode::new_modified(serializations)),
+            ("deserializations".to_string(), RewriteNode::new_modified(deserializations)),
+        ]
+        .into(),
+    )
+}
+
+/// Returns the code deserializing the wrapper argument `arg_name`, of type `$type_name$`, from
+/// `data`. See [generate_entry_point_wrapper] for `result_output`.
+pub fn arg_definition_code(arg_name: &str, mut_modifier: &str, result_output: bool) -> String {
+    let input_data_short_err = "'Input too short for arguments'";
+    // TODO(yuval): use panicable version of deserializations when supported.
+    if result_output {
+        format!(
+            "
+            let {mut_modifier}{arg_name} = match serde::Serde::<$type_name$>::deserialize(ref \
+             data) {{
+                Option::Some(value) => value,
+                Option::None(()) => {{{}
+                }},
+            }};",
+            return_err_code(input_data_short_err, "                    ")
+        )
+    } else {
+        format!(
+            "
+            let {mut_modifier}{arg_name} =
+                serde::Serde::<$type_name$>::deserialize(ref data).expect({input_data_short_err});"
+        )
+    }
+}
+
+/// Returns the code checking that `expected_length` felts remain in `data` after the argument
+/// `param_name` is deserialized. See [generate_entry_point_wrapper] for `result_output`.
+fn remaining_length_check_code(
+    param_name: &str,
+    expected_length: usize,
+    result_output: bool,
+) -> String {
+    let fail = if result_output { "return Result::Err(err_data);" } else { "panic(err_data);" };
+    format!(
+        "
+            if array::SpanTrait::len(data) != {expected_length} {{
+                let mut err_data = array::array_new();
+                array::array_append(ref err_data, 'Bad calldata length after arg');
+                array::array_append(ref err_data, '{param_name}');
+                {fail}
+            }}"
+    )
+}
+
+/// Returns the number of felts a value of the given type is serialized into, if it is known
+/// syntactically.
+fn serialized_size(db: &dyn SyntaxGroup, type_ast: &ast::Expr) -> Option<usize> {
+    match type_ast {
+        ast::Expr::Parenthesized(expr) => serialized_size(db, &expr.expr(db)),
+        ast::Expr::Tuple(tuple) => {
+            tuple.expressions(db).elements(db).iter().map(|ty| serialized_size(db, ty)).sum()
+        }
+        ast::Expr::Path(path) => match path.as_syntax_node().get_text_without_trivia(db).as_str() {
+            "felt252" | "bool" | "u8" | "u16" | "u32" | "u64" | "u128" | "ContractAddress"
+            | "starknet::ContractAddress" | "ClassHash" | "starknet::ClassHash" | "EthAddress"
+            | "starknet::EthAddress" | "StorageAddress" | "starknet::StorageAddress" => Some(1),
+            "u256" => Some(2),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// Returns the inner type of a snapshot type expression, i.e. `T` for `@T`.
+fn try_extract_snapshot_inner_type(
+    db: &dyn SyntaxGroup,
+    type_ast: &ast::Expr,
+) -> Option<ast::Expr> {
+    let ast::Expr::Unary(unary) = type_ast else {
+        return None;
+    };
+    match unary.op(db) {
+        ast::UnaryOperator::At(_) => Some(unary.expr(db)),
+        _ => None,
+    }
+}
+
+/// Generates the code of an entry point wrapper named `function_name`, given the code deserializing
+/// its arguments from `data` and the code calling the wrapped function and returning its output.
+/// See [generate_entry_point_wrapper] for `result_output`.
+/// If `gas_check` is false, no gas is withdrawn before the arguments are deserialized, which is
+/// only valid if there are none. The gas withdrawal before the call is always generated.
+/// If `felt252_panics` is true, a panicking wrapper given too long calldata fails with
+/// `panic_with_felt252` instead of building the panic data array.
+/// `implicits_clause` is the implicits clause of the wrapper, which declares the implicits declared
+/// by the wrapped function, or is empty.
+pub fn generate_wrapper_code(
+    function_name: RewriteNode,
+    arg_definitions: RewriteNode,
+    output_handling: RewriteNode,
+    implicits_clause: RewriteNode,
+    result_output: bool,
+    gas_check: bool,
+    felt252_panics: bool,
+) -> RewriteNode {
     let implicit_precedence = RewriteNode::Text(format!("#[implicit_precedence({})]", {
         IMPLICIT_PRECEDENCE.iter().join(", ")
     }));
 
-    let arg_definitions = RewriteNode::Text(arg_definitions.join("\n"));
-
-    Ok(RewriteNode::interpolate_patched(
-        "$implicit_precedence$
-        fn $function_name$(mut data: Span::<felt252>) -> Span::<felt252> {
-            internal::revoke_ap_tracking();
-            gas::withdraw_gas().expect('Out of gas');
+    let template = if result_output {
+        format!(
+            "$implicit_precedence$
+        fn $function_name$(mut data: Span::<felt252>) -> Result::<Span::<felt252>, \
+             Array::<felt252>>$implicits_clause$ {{
+            internal::revoke_ap_tracking();{gas_check}
             $arg_definitions$
-            if !array::SpanTrait::is_empty(data) {
+            if !array::SpanTrait::is_empty(data) {{
                 // Force the inclusion of `System` in the list of implicits.
                 starknet::use_system_implicit();
-
+{input_too_long}
+            }}
+            if gas::withdraw_gas_all(get_builtin_costs()).is_none() {{{out_of_gas}
+            }}
+            let mut storage = super::unsafe_new_contract_state();
+            $output_handling$
+        }}",
+            gas_check = if gas_check {
+                format!(
+                    "
+            if gas::withdraw_gas().is_none() {{{}
+            }}",
+                    return_err_code("'Out of gas'", "                ")
+                )
+            } else {
+                "".to_string()
+            },
+            out_of_gas = return_err_code("'Out of gas'", "                "),
+            input_too_long = return_err_code("'Input too long for arguments'", "                "),
+        )
+    } else {
+        let gas_check =
+            if gas_check { "\n            gas::withdraw_gas().expect('Out of gas');" } else { "" };
+        let input_too_long = if felt252_panics {
+            "
+                panic_with_felt252('Input too long for arguments');"
+        } else {
+            "
                 let mut err_data = array::array_new();
                 array::array_append(ref err_data, 'Input too long for arguments');
-                panic(err_data);
-            }
+                panic(err_data);"
+        };
+        format!(
+            "$implicit_precedence$
+        fn $function_name$(mut data: Span::<felt252>) -> Span::<felt252>$implicits_clause$ {{
+            internal::revoke_ap_tracking();{gas_check}
+            $arg_definitions$
+            if !array::SpanTrait::is_empty(data) {{
+                // Force the inclusion of `System` in the list of implicits.
+                starknet::use_system_implicit();
+{input_too_long}
+            }}
             gas::withdraw_gas_all(get_builtin_costs()).expect('Out of gas');
             let mut storage = super::unsafe_new_contract_state();
             $output_handling$
-        }",
+        }}"
+        )
+    };
+
+    RewriteNode::interpolate_patched(
+        &template,
         [
             ("function_name".to_string(), function_name),
             ("output_handling".to_string(), output_handling),
             ("arg_definitions".to_string(), arg_definitions),
             ("implicit_precedence".to_string(), implicit_precedence),
+            ("implicits_clause".to_string(), implicits_clause),
         ]
         .into(),
-    ))
+    )
+}
+
+/// Validates that the entry point has at most `max_params` parameters, excluding `self`.
+pub fn validate_param_count(
+    db: &dyn SyntaxGroup,
+    diagnostics: &mut Vec<PluginDiagnostic>,
+    function: &FunctionWithBody,
+    max_params: usize,
+) {
+    let declaration = function.declaration(db);
+    let param_count = declaration.signature(db).parameters(db).elements(db).len();
+    if param_count.saturating_sub(1) > max_params {
+        diagnostics.push(PluginDiagnostic {
+            message: format!(
+                "Entry points are limited to {max_params} parameters, excluding `self`. Consider \
+                 grouping parameters into a struct."
+            ),
+            stable_ptr: declaration.name(db).stable_ptr().untyped(),
+        });
+    }
+}
+
+/// Returns the implicits declared by the entry point that can be threaded through its wrapper, i.e.
+/// those in IMPLICIT_PRECEDENCE. The wrapper declares them as well. Reports the other implicits.
+fn supported_implicits(
+    db: &dyn SyntaxGroup,
+    diagnostics: &mut Vec<PluginDiagnostic>,
+    sig: &ast::FunctionSignature,
+) -> Vec<ast::ExprPath> {
+    let OptionImplicitsClause::ImplicitsClause(implicits_clause) = sig.implicits_clause(db) else {
+        return vec![];
+    };
+    let mut implicits = vec![];
+    for implicit in implicits_clause.implicits(db).elements(db) {
+        // Compare only the last segment, to allow both `RangeCheck` and `core::RangeCheck`.
+        let is_supported = match implicit.elements(db).last() {
+            Some(ast::PathSegment::Simple(segment)) => {
+                IMPLICIT_PRECEDENCE.contains(&segment.ident(db).text(db).as_str())
+            }
+            _ => false,
+        };
+        if is_supported {
+            implicits.push(implicit);
+        } else {
+            diagnostics.push(PluginDiagnostic {
+                message: format!(
+                    "Entry points can only declare the implicits: {}.",
+                    IMPLICIT_PRECEDENCE.join(", ")
+                ),
+                stable_ptr: implicit.stable_ptr().untyped(),
+            });
+        }
+    }
+    implicits
+}
+
+/// Validates that the arrays in the given type are not nested too deeply to be serialized by an
+/// entry point.
+fn validate_array_nesting_depth(
+    db: &dyn SyntaxGroup,
+    diagnostics: &mut Vec<PluginDiagnostic>,
+    type_ast: &ast::Expr,
+) {
+    if array_nesting_depth(db, type_ast) > MAX_ARRAY_NESTING_DEPTH {
+        diagnostics.push(PluginDiagnostic {
+            message: format!(
+                "Arrays nested more than {MAX_ARRAY_NESTING_DEPTH} levels deep are not supported \
+                 in entry points."
+            ),
+            stable_ptr: type_ast.stable_ptr().untyped(),
+        });
+    }
+}
+
+/// Returns the code that returns an `Err` with the given short string error from a wrapper
+/// generated with `result_output`, indented by `indent`.
+fn return_err_code(err: &str, indent: &str) -> String {
+    format!(
+        "
+{indent}let mut err_data = array::array_new();
+{indent}array::array_append(ref err_data, {err});
+{indent}return Result::Err(err_data);"
+    )
 }
 
 /// Checks if the item is marked with an external attribute. Also validates the attribute.
 pub fn has_external_attribute(
     db: &dyn SyntaxGroup,
     diagnostics: &mut Vec<PluginDiagnostic>,
-    item: &ast::Item,
+    item: &impl QueryAttrs,
 ) -> bool {
     let Some(attr) = item.find_attr(db, EXTERNAL_ATTR) else { return false; };
     validate_external_v0(db, diagnostics, &attr);
@@ -223,9 +874,16 @@ pub fn validate_external_v0(
     attr: &Attribute,
 ) {
     if !is_arg_v0(db, attr) {
+        // Point at the unsupported arguments, if there are any.
+        let stable_ptr = match attr.arguments(db) {
+            OptionArgListParenthesized::ArgListParenthesized(args) => {
+                args.args(db).stable_ptr().untyped()
+            }
+            OptionArgListParenthesized::Empty(_) => attr.stable_ptr().untyped(),
+        };
         diagnostics.push(PluginDiagnostic {
             message: "Only #[external(v0)] is supported.".to_string(),
-            stable_ptr: attr.stable_ptr().untyped(),
+            stable_ptr,
         });
     }
 }
@@ -68,8 +68,9 @@ pub fn handle_struct(db: &dyn SyntaxGroup, struct_ast: ast::ItemStruct) -> Plugi
             "self.$member_name$",
             [(String::from("member_name"), member_name.clone())].into(),
         );
-        let append_member = append_field(member_kind, member_for_append);
-        let deserialize_member = deserialize_field(member_kind, member_name.clone());
+        let member_type = RewriteNode::new_trimmed(member.type_clause(db).ty(db).as_syntax_node());
+        let append_member = append_field(member_kind, member_for_append, member_type.clone());
+        let deserialize_member = deserialize_field(member_kind, member_name.clone(), member_type);
         append_members.push(append_member);
         deserialize_members.push(deserialize_member);
         ctor.push(RewriteNode::interpolate_patched(
@@ -155,6 +156,8 @@ fn get_field_kind(
 }
 
 /// Derive the `Event` trait for enums annotated with `derive(starknet::Event)`.
+/// The first key of an emitted variant is the `starknet_keccak` of the variant name, e.g. of
+/// `Transfer` for `Event::Transfer(Transfer { .. })`, followed by the keys of its value.
 pub fn handle_enum(db: &dyn SyntaxGroup, enum_ast: ast::ItemEnum) -> PluginResult {
     if !derive_event_needed(&enum_ast, db) {
         return PluginResult::default();
@@ -183,7 +186,10 @@ pub fn handle_enum(db: &dyn SyntaxGroup, enum_ast: ast::ItemEnum) -> PluginResul
         let variant_selector = format!("0x{:x}", starknet_keccak(name.as_bytes()));
         let member_kind = get_field_kind(db, &mut diagnostics, &variant, EventFieldKind::Nested);
         variants.push((name, member_kind));
-        let append_member = append_field(member_kind, RewriteNode::Text("val".into()));
+        let variant_type =
+            RewriteNode::new_trimmed(variant.type_clause(db).ty(db).as_syntax_node());
+        let append_member =
+            append_field(member_kind, RewriteNode::Text("val".into()), variant_type.clone());
         let append_variant = RewriteNode::interpolate_patched(
             "
             $enum_name$::$variant_name$(val) => {
@@ -197,7 +203,8 @@ pub fn handle_enum(db: &dyn SyntaxGroup, enum_ast: ast::ItemEnum) -> PluginResul
             ]
             .into(),
         );
-        let deserialize_member = deserialize_field(member_kind, RewriteNode::Text("val".into()));
+        let deserialize_member =
+            deserialize_field(member_kind, RewriteNode::Text("val".into()), variant_type);
         let deserialize_variant = RewriteNode::interpolate_patched(
             "
             if selector == $variant_selector$ {$deserialize_member$
@@ -285,7 +292,11 @@ pub fn derive_event_needed<T: QueryAttrs>(with_attrs: &T, db: &dyn SyntaxGroup)
 }
 
 /// Generates code to emit an event for a field
-fn append_field(member_kind: EventFieldKind, field: RewriteNode) -> RewriteNode {
+fn append_field(
+    member_kind: EventFieldKind,
+    field: RewriteNode,
+    field_type: RewriteNode,
+) -> RewriteNode {
     match member_kind {
         EventFieldKind::Nested => RewriteNode::interpolate_patched(
             "
@@ -296,18 +307,22 @@ fn append_field(member_kind: EventFieldKind, field: RewriteNode) -> RewriteNode
         ),
         EventFieldKind::KeySerde => RewriteNode::interpolate_patched(
             "
-                serde::Serde::serialize($field$, ref keys);",
-            [(String::from("field"), field)].into(),
+                serde::Serde::<$field_type$>::serialize($field$, ref keys);",
+            [(String::from("field"), field), (String::from("field_type"), field_type)].into(),
         ),
         EventFieldKind::DataSerde => RewriteNode::interpolate_patched(
             "
-                serde::Serde::serialize($field$, ref data);",
-            [(String::from("field"), field)].into(),
+                serde::Serde::<$field_type$>::serialize($field$, ref data);",
+            [(String::from("field"), field), (String::from("field_type"), field_type)].into(),
         ),
     }
 }
 
-fn deserialize_field(member_kind: EventFieldKind, member_name: RewriteNode) -> RewriteNode {
+fn deserialize_field(
+    member_kind: EventFieldKind,
+    member_name: RewriteNode,
+    member_type: RewriteNode,
+) -> RewriteNode {
     match member_kind {
         EventFieldKind::Nested => RewriteNode::interpolate_patched(
             "
@@ -318,17 +333,19 @@ fn deserialize_field(member_kind: EventFieldKind, member_name: RewriteNode) -> R
         ),
         EventFieldKind::KeySerde => RewriteNode::interpolate_patched(
             "
-                let $member_name$ = serde::Serde::deserialize(
+                let $member_name$ = serde::Serde::<$member_type$>::deserialize(
                     ref keys
                 )?;",
-            [(String::from("member_name"), member_name)].into(),
+            [(String::from("member_name"), member_name), (String::from("member_type"), member_type)]
+                .into(),
         ),
         EventFieldKind::DataSerde => RewriteNode::interpolate_patched(
             "
-                let $member_name$ = serde::Serde::deserialize(
+                let $member_name$ = serde::Serde::<$member_type$>::deserialize(
                     ref data
                 )?;",
-            [(String::from("member_name"), member_name)].into(),
+            [(String::from("member_name"), member_name), (String::from("member_type"), member_type)]
+                .into(),
         ),
     }
 }
@@ -4,7 +4,9 @@ mod test;
 pub mod consts;
 use std::sync::Arc;
 
-use cairo_lang_defs::plugin::{MacroPlugin, PluginResult};
+use cairo_lang_defs::ids::{ModuleFileId, ModuleId};
+use cairo_lang_defs::plugin::{MacroPlugin, PluginDiagnostic, PluginResult};
+use cairo_lang_semantic::db::SemanticGroup;
 use cairo_lang_semantic::plugin::{AsDynMacroPlugin, SemanticPlugin};
 use cairo_lang_syntax::node::ast;
 use cairo_lang_syntax::node::db::SyntaxGroup;
@@ -19,17 +21,117 @@ pub mod events;
 mod storage;
 mod storage_access;
 mod utils;
+mod views;
 
 use dispatcher::handle_trait;
 use events::derive_event_needed;
 use storage_access::derive_storage_access_needed;
 
-use self::contract::{handle_contract_by_storage, handle_module};
+use self::contract::{handle_contract_by_storage, handle_module, is_migrated_storage_struct};
 use self::events::handle_enum;
+use self::views::view_diagnostics;
 
+/// The Starknet macro plugin.
+///
+/// Each contract module is expanded on its own into a generated `contract` file, namespaced by the
+/// contract module. The expansion of an item can't depend on other items, as `generate_code` is
+/// queried (and cached) per item.
+///
+/// The entry points stay in the contract module, next to the ABI trait. Their wrappers, which
+/// deserialize the arguments, call the entry point and serialize the output, are generated in
+/// submodules of the contract module: `__external`, `__l1_handler` and `__constructor`. Hence the
+/// wrappers call the entry points one module up, e.g. `super::foo`. The contract itself calls them
+/// directly, e.g. `foo(@self)`. With `#[abi_impl]` on the contract, the ABI trait is implemented by
+/// delegating to the entry points. The wrappers then call them through that impl, and the contract
+/// may call them as methods, e.g. `self.foo()`. Entry points annotated with `#[interface(Name)]`
+/// are listed in a separate `__abi_Name` trait, implemented by `__abi_impl_Name`.
 #[derive(Debug, Default)]
 #[non_exhaustive]
-pub struct StarkNetPlugin;
+pub struct StarkNetPlugin {
+    /// The maximal number of parameters of an entry point, excluding `self`. Not limited if None.
+    max_entry_point_params: Option<usize>,
+    /// Whether to report contracts with storage members but without a constructor.
+    lint_missing_constructor: bool,
+    /// Whether to generate an empty constructor for contracts without one.
+    default_constructor: bool,
+    /// Whether the wrappers fail with `panic_with_felt252` when a single felt252 describes the
+    /// failure, instead of building the panic data array.
+    felt252_panics: bool,
+    /// Whether to omit the gas check before the deserialization of the arguments from the wrappers
+    /// of entry points without arguments.
+    minimal_wrappers: bool,
+    /// The maximal length in bytes of the code generated for a contract. Not limited if None.
+    max_generated_contract_size: Option<usize>,
+}
+impl StarkNetPlugin {
+    /// Returns a builder of a configured plugin.
+    pub fn builder() -> StarkNetPluginBuilder {
+        StarkNetPluginBuilder::default()
+    }
+}
+
+/// A builder of a configured [StarkNetPlugin]. Without configuration, builds
+/// `StarkNetPlugin::default()`.
+#[derive(Debug, Default)]
+pub struct StarkNetPluginBuilder {
+    plugin: StarkNetPlugin,
+}
+impl StarkNetPluginBuilder {
+    /// Reports entry points with more than `max_entry_point_params` parameters, excluding `self`.
+    /// Plugin diagnostics have no severity, so the report is an error failing the compilation, not
+    /// a warning.
+    pub fn with_max_entry_point_params(mut self, max_entry_point_params: usize) -> Self {
+        self.plugin.max_entry_point_params = Some(max_entry_point_params);
+        self
+    }
+
+    /// Reports contracts with storage members but without a constructor, as their storage may be
+    /// left uninitialized. Plugin diagnostics have no severity, so the report is an error failing
+    /// the compilation, not a warning. Contracts whose storage is intentionally zero initialized
+    /// must be annotated with ALLOW_MISSING_CONSTRUCTOR_ATTR.
+    pub fn with_missing_constructor_lint(mut self) -> Self {
+        self.plugin.lint_missing_constructor = true;
+        self
+    }
+
+    /// Generates an empty constructor for contracts without one, so that every contract has a
+    /// constructor entry point. Like any constructor without parameters, it fails if given
+    /// calldata.
+    pub fn with_default_constructor(mut self) -> Self {
+        self.plugin.default_constructor = true;
+        self
+    }
+
+    /// Generates wrappers failing with `panic_with_felt252` instead of building the panic data
+    /// array when a single felt252 describes the failure, such as 'Input too long for arguments',
+    /// which shortens the generated code. The panic data is the same either way.
+    pub fn with_felt252_panics(mut self) -> Self {
+        self.plugin.felt252_panics = true;
+        self
+    }
+
+    /// Omits the gas check before the deserialization of the arguments from the wrappers of entry
+    /// points without arguments besides `self`, as it only pays for the deserialization. The gas
+    /// check before the call of the entry point is kept, and pays for the entry point whatever its
+    /// body. Entry points annotated with KEEP_GAS_CHECK_ATTR keep both checks.
+    pub fn with_minimal_wrappers(mut self) -> Self {
+        self.plugin.minimal_wrappers = true;
+        self
+    }
+
+    /// Reports contracts whose generated code is longer than `max_generated_contract_size` bytes,
+    /// as such contracts are slow to compile and should usually be split. Plugin diagnostics have
+    /// no severity, so the report is an error failing the compilation, not a warning.
+    pub fn with_max_generated_contract_size(mut self, max_generated_contract_size: usize) -> Self {
+        self.plugin.max_generated_contract_size = Some(max_generated_contract_size);
+        self
+    }
+
+    /// Returns the configured plugin.
+    pub fn build(self) -> StarkNetPlugin {
+        self.plugin
+    }
+}
 
 impl MacroPlugin for StarkNetPlugin {
     fn generate_code(&self, db: &dyn SyntaxGroup, item_ast: ast::Item) -> PluginResult {
@@ -37,20 +139,53 @@ impl MacroPlugin for StarkNetPlugin {
             ast::Item::Module(module_ast) => handle_module(db, module_ast),
             ast::Item::Trait(trait_ast) => handle_trait(db, trait_ast),
             ast::Item::Struct(struct_ast) if derive_event_needed(&struct_ast, db) => {
-                events::handle_struct(db, struct_ast)
+                let mut result = events::handle_struct(db, struct_ast.clone());
+                if derive_storage_access_needed(&struct_ast, db) {
+                    append_plugin_result(
+                        &mut result,
+                        storage_access::handle_struct(db, struct_ast),
+                    );
+                }
+                result
             }
             ast::Item::Struct(struct_ast) if derive_storage_access_needed(&struct_ast, db) => {
                 storage_access::handle_struct(db, struct_ast)
             }
             ast::Item::Struct(struct_ast) if struct_ast.has_attr(db, STORAGE_ATTR) => {
-                handle_contract_by_storage(db, struct_ast).unwrap_or_default()
+                handle_contract_by_storage(db, self, struct_ast).unwrap_or_default()
+            }
+            ast::Item::Struct(struct_ast) if is_migrated_storage_struct(db, &struct_ast) => {
+                PluginResult { remove_original_item: true, ..PluginResult::default() }
+            }
+            ast::Item::Enum(enum_ast) => {
+                let mut result = handle_enum(db, enum_ast.clone());
+                if derive_storage_access_needed(&enum_ast, db) {
+                    append_plugin_result(&mut result, storage_access::handle_enum(db, enum_ast));
+                }
+                result
             }
-            ast::Item::Enum(enum_ast) => handle_enum(db, enum_ast),
             // Nothing to do for other items.
             _ => PluginResult::default(),
         }
     }
 }
+/// Appends the code and diagnostics of `other` to `result`, for items deriving several traits. The
+/// code of `other` must not have patches, e.g. the `StorageAccess` impls, as they would be lost.
+/// The patches of `result` stay valid, as its code is only extended.
+fn append_plugin_result(result: &mut PluginResult, other: PluginResult) {
+    result.diagnostics.extend(other.diagnostics);
+    match (&mut result.code, other.code) {
+        (Some(code), Some(other_code)) => {
+            if !code.content.ends_with('\n') {
+                code.content.push('\n');
+            }
+            code.content.push_str(&other_code.content);
+        }
+        (code @ None, other_code) => *code = other_code,
+        (Some(_), None) => {}
+    }
+}
+
 impl AsDynMacroPlugin for StarkNetPlugin {
     fn as_dyn_macro_plugin<'a>(self: Arc<Self>) -> Arc<dyn MacroPlugin + 'a>
     where
@@ -59,4 +194,12 @@ impl AsDynMacroPlugin for StarkNetPlugin {
         self
     }
 }
-impl SemanticPlugin for StarkNetPlugin {}
+impl SemanticPlugin for StarkNetPlugin {
+    fn module_semantic_diagnostics(
+        &self,
+        db: &dyn SemanticGroup,
+        module_id: ModuleId,
+    ) -> Vec<(ModuleFileId, PluginDiagnostic)> {
+        view_diagnostics(db, module_id)
+    }
+}
@@ -1,90 +1,339 @@
 use cairo_lang_defs::plugin::PluginDiagnostic;
 use cairo_lang_semantic::patcher::RewriteNode;
+use cairo_lang_syntax::node::ast::OptionArgListParenthesized;
 use cairo_lang_syntax::node::db::SyntaxGroup;
+use cairo_lang_syntax::node::helpers::QueryAttrs;
 use cairo_lang_syntax::node::{ast, Terminal, TypedSyntaxNode};
 use cairo_lang_utils::try_extract_matches;
 use cairo_lang_utils::unordered_hash_map::UnorderedHashMap;
 use indoc::formatdoc;
+use itertools::Itertools;
+use num_bigint::BigUint;
+use smol_str::SmolStr;
 
+use super::consts::{
+    EXTERNAL_ATTR, FLAT_ATTR, GETTER_ATTR, GETTER_FUNCTION_PREFIX, HASH_ATTR, INLINE_ACCESSORS_ATTR,
+    MAX_STORAGE_MEMBER_SLOTS, MIGRATE_FROM_ATTR, OPTIONAL_READS_ATTR, RAW_OUTPUT_ATTR,
+    READ_FAILURE_ATTR, STORAGE_GETTER_FUNCTION, STORAGE_MIGRATE_FUNCTION, STORAGE_MODULE,
+    STORAGE_RESET_FUNCTION, STORAGE_SLOT_ATTR,
+};
+use super::aux_data::StorageMemberLayout;
+use super::storage_access::derive_storage_access_needed;
+use super::utils::{forbid_attribute_args, try_extract_felt252_literal, validate_name_not_reserved};
 use crate::contract::starknet_keccak;
 
-/// Generate getters and setters for the variables in the storage struct.
+/// The structs declared in the contract module that don't implement `StorageAccess`, by name.
+/// Structs marked with FLAT_ATTR are given, as a storage member of their type is stored as a member
+/// per field.
+pub type ContractStructs = UnorderedHashMap<SmolStr, Option<ast::ItemStruct>>;
+
+/// Collects the ContractStructs of the given items of a contract module. A struct implements
+/// `StorageAccess` if it derives it, or if one of the items implements it for the struct.
+pub fn collect_contract_structs(
+    db: &dyn SyntaxGroup,
+    diagnostics: &mut Vec<PluginDiagnostic>,
+    items: &[ast::Item],
+) -> ContractStructs {
+    let mut contract_structs = ContractStructs::default();
+    let mut storage_access_impls = vec![];
+    for item in items {
+        match item {
+            ast::Item::Struct(item_struct) if item_struct.has_attr(db, FLAT_ATTR) => {
+                forbid_attribute_args(db, diagnostics, item_struct, &[FLAT_ATTR]);
+                contract_structs.insert(item_struct.name(db).text(db), Some(item_struct.clone()));
+            }
+            ast::Item::Struct(item_struct) if !derive_storage_access_needed(item_struct, db) => {
+                contract_structs.insert(item_struct.name(db).text(db), None);
+            }
+            ast::Item::Impl(item_impl) => {
+                let Some(ast::PathSegment::WithGenericArgs(segment)) =
+                    item_impl.trait_path(db).elements(db).pop() else {
+                    continue;
+                };
+                if segment.ident(db).text(db) != "StorageAccess" {
+                    continue;
+                }
+                if let [ast::GenericArg::Expr(arg)] =
+                    &segment.generic_args(db).generic_args(db).elements(db)[..]
+                {
+                    storage_access_impls.push(struct_type_name(db, &arg.value(db)));
+                }
+            }
+            _ => {}
+        }
+    }
+    for type_name in storage_access_impls {
+        if let Some(None) = contract_structs.get(&type_name) {
+            contract_structs.remove(&type_name);
+        }
+    }
+    contract_structs
+}
+
+/// Generate getters and setters for the variables in the storage struct, in a module per member
+/// inside the STORAGE_MODULE module. `extra_uses_node` holds the uses of the member modules.
+/// Also returns the storage layout of the supported members.
 pub fn handle_storage_struct(
     db: &dyn SyntaxGroup,
     struct_ast: ast::ItemStruct,
+    contract_structs: &ContractStructs,
     extra_uses_node: &RewriteNode,
     has_event: bool,
-) -> (RewriteNode, Vec<PluginDiagnostic>) {
+) -> (RewriteNode, Vec<PluginDiagnostic>, Vec<StorageMemberLayout>) {
     let mut members_code = Vec::new();
     let mut members_init_code = Vec::new();
+    let mut member_uses_code = Vec::new();
     let mut vars_code = Vec::new();
     let mut diagnostics = vec![];
+    let mut storage_layout = vec![];
+    // The name, base address and slot count, if known, of the non mapping members.
+    let mut simple_members = vec![];
+    // The members pinned by STORAGE_SLOT_ATTR, by their slot.
+    let mut pinned_slots = UnorderedHashMap::<BigUint, SmolStr>::default();
+    let inline = if struct_ast.has_attr(db, INLINE_ACCESSORS_ATTR) {
+        "#[inline(always)]\n            "
+    } else {
+        ""
+    };
 
     for member in struct_ast.members(db).elements(db) {
+        let type_ast = member.type_clause(db).ty(db);
+        if let ast::Expr::Missing(_) = type_ast {
+            // Already reported by the parser.
+            continue;
+        }
+        if !is_storable_type_expr(db, &type_ast) {
+            diagnostics.push(PluginDiagnostic {
+                message: "Unsupported storage member type.".to_string(),
+                stable_ptr: type_ast.stable_ptr().untyped(),
+            });
+            continue;
+        }
+        let tuple_components = try_extract_tuple_components(db, &type_ast);
+        if let Some(components) = &tuple_components {
+            let multi_slot_components = components
+                .iter()
+                .filter(|component| storage_member_slots(db, component) != 1)
+                .collect_vec();
+            for component in &multi_slot_components {
+                diagnostics.push(PluginDiagnostic {
+                    message: "Tuple storage members only support single slot components."
+                        .to_string(),
+                    stable_ptr: component.stable_ptr().untyped(),
+                });
+            }
+            if !multi_slot_components.is_empty() {
+                continue;
+            }
+        }
+        // The accessors of a member are generated in a module named after it, inside
+        // STORAGE_MODULE.
+        if !validate_name_not_reserved(db, &mut diagnostics, &member.name(db), "a storage member") {
+            continue;
+        }
+        let flat_struct = match contract_structs.get(&struct_type_name(db, &type_ast)) {
+            Some(Some(flat_struct)) => Some(flat_struct),
+            Some(None) => {
+                diagnostics.push(unsupported_struct_diagnostic(&type_ast));
+                continue;
+            }
+            None => None,
+        };
         let name_node = member.name(db).as_syntax_node();
         let name = member.name(db).text(db);
         members_code.push(RewriteNode::interpolate_patched(
-            "
-        $name$: $name$::ContractState,",
+            &format!(
+                "
+        $name$: {STORAGE_MODULE}::$name$::ContractState,"
+            ),
             UnorderedHashMap::from([(
                 "name".to_string(),
                 RewriteNode::new_trimmed(name_node.clone()),
             )]),
         ));
-        members_init_code.push(RewriteNode::interpolate_patched(
-            "
-            $name$: $name$::ContractState{},",
-            UnorderedHashMap::from([("name".to_string(), RewriteNode::new_trimmed(name_node))]),
-        ));
-        let address = format!("0x{:x}", starknet_keccak(name.as_bytes()));
-        let type_ast = member.type_clause(db).ty(db);
-        match try_extract_mapping_types(db, &type_ast) {
-            Some((key_type_ast, value_type_ast, MappingType::Legacy)) => {
-                vars_code.push(RewriteNode::interpolate_patched(
-                    handle_legacy_mapping_storage_var(&address).as_str(),
-                    [
-                        (
-                            "storage_var_name".to_string(),
-                            RewriteNode::new_trimmed(member.name(db).as_syntax_node()),
-                        ),
-                        ("extra_uses".to_string(), extra_uses_node.clone()),
-                        (
-                            "key_type".to_string(),
-                            RewriteNode::new_trimmed(key_type_ast.as_syntax_node()),
-                        ),
-                        (
-                            "value_type".to_string(),
-                            RewriteNode::new_trimmed(value_type_ast.as_syntax_node()),
+        if flat_struct.is_none() {
+            members_init_code.push(RewriteNode::interpolate_patched(
+                &format!(
+                    "
+            $name$: {STORAGE_MODULE}::$name$::ContractState{{}},"
+                ),
+                UnorderedHashMap::from([(
+                    "name".to_string(),
+                    RewriteNode::new_trimmed(name_node.clone()),
+                )]),
+            ));
+            member_uses_code.push(RewriteNode::interpolate_patched(
+                &format!(
+                    "
+    use {STORAGE_MODULE}::$name$::InternalContractStateTrait as $name$ContractStateTrait;"
+                ),
+                UnorderedHashMap::from([(
+                    "name".to_string(),
+                    RewriteNode::new_trimmed(name_node.clone()),
+                )]),
+            ));
+        }
+        let mut is_pinned_twice = false;
+        let base_address = match get_storage_slot(db, &mut diagnostics, &member) {
+            Some(slot) => {
+                if let Some(pinning_name) = pinned_slots.get(&slot) {
+                    diagnostics.push(PluginDiagnostic {
+                        message: format!(
+                            "The storage slot of '{name}' is already pinned by '{pinning_name}'."
                         ),
-                    ]
-                    .into(),
-                ));
+                        stable_ptr: member.name(db).stable_ptr().untyped(),
+                    });
+                    is_pinned_twice = true;
+                } else {
+                    pinned_slots.insert(slot.clone(), name.clone());
+                }
+                slot
             }
+            None => starknet_keccak(name.as_bytes()),
+        };
+        let address = format!("0x{base_address:x}");
+        if let Some(flat_struct) = flat_struct {
+            // Reports the misuse of the mapping attributes.
+            get_key_hash(db, &mut diagnostics, &member, false);
+            has_optional_reads(db, &mut diagnostics, &member, false);
+            let read_failure = get_read_failure(db, &mut diagnostics, &member);
+            let init_code = handle_flat_storage_var(
+                db,
+                &mut diagnostics,
+                contract_structs,
+                extra_uses_node,
+                FlatMember {
+                    path: name.to_string(),
+                    module_name: name.to_string(),
+                    address,
+                    flat_struct: flat_struct.clone(),
+                    enclosing_structs: vec![],
+                    inline,
+                    read_failure,
+                },
+                &mut vars_code,
+                &mut member_uses_code,
+            );
+            members_init_code.push(RewriteNode::interpolate_patched(
+                &format!(
+                    "
+            $name$: {init_code},"
+                ),
+                UnorderedHashMap::from([(
+                    "name".to_string(),
+                    RewriteNode::new_trimmed(name_node),
+                )]),
+            ));
+            storage_layout.push(StorageMemberLayout {
+                name: name.clone(),
+                ty: type_ast.as_syntax_node().get_text_without_trivia(db),
+                base_address,
+                slots: None,
+            });
+            continue;
+        }
+        // The value type node is shared by the read and write accessors, so their types can't
+        // diverge.
+        let mapping_types = try_extract_mapping_types(db, &type_ast);
+        let key_hash = get_key_hash(db, &mut diagnostics, &member, mapping_types.is_some());
+        let optional_reads =
+            has_optional_reads(db, &mut diagnostics, &member, mapping_types.is_some());
+        let read_failure = get_read_failure(db, &mut diagnostics, &member);
+        let (template, key_type, value_type, slots) = match mapping_types {
+            Some((key_type_ast, value_type_ast, MappingType::Legacy)) => (
+                handle_legacy_mapping_storage_var(
+                    &name,
+                    &address,
+                    inline,
+                    key_hash,
+                    read_failure,
+                    optional_reads,
+                ),
+                Some(RewriteNode::new_trimmed(key_type_ast.as_syntax_node())),
+                RewriteNode::new_trimmed(value_type_ast.as_syntax_node()),
+                None,
+            ),
             Some((_, _, MappingType::NonLegacy)) => {
                 diagnostics.push(PluginDiagnostic {
                     message: "Non `LegacyMap` mapping is not yet supported.".to_string(),
                     stable_ptr: type_ast.stable_ptr().untyped(),
                 });
+                continue;
             }
             None => {
-                vars_code.push(RewriteNode::interpolate_patched(
-                    handle_simple_storage_var(&address).as_str(),
-                    [
-                        (
-                            "storage_var_name".to_string(),
-                            RewriteNode::new_trimmed(member.name(db).as_syntax_node()),
+                let known_slots = known_storage_member_slots(db, &type_ast);
+                let slots = known_slots.unwrap_or(MAX_STORAGE_MEMBER_SLOTS);
+                // Members pinned to the same slot are already reported.
+                if !is_pinned_twice {
+                    simple_members.push((member.name(db), base_address.clone(), known_slots));
+                }
+                let address_description = format!("address `{address}`");
+                let address_code = format!("starknet::storage_base_address_const::<{address}>()");
+                (
+                    match &tuple_components {
+                        Some(components) => handle_tuple_storage_var(
+                            &name,
+                            &address_description,
+                            &address_code,
+                            inline,
+                            read_failure,
+                            components.len(),
                         ),
-                        ("extra_uses".to_string(), extra_uses_node.clone()),
-                        (
-                            "type_name".to_string(),
-                            RewriteNode::new_trimmed(type_ast.as_syntax_node()),
+                        None => handle_simple_storage_var(
+                            &name,
+                            &address_description,
+                            &address_code,
+                            inline,
+                            read_failure,
                         ),
-                    ]
-                    .into(),
-                ));
+                    },
+                    None,
+                    RewriteNode::new_trimmed(type_ast.as_syntax_node()),
+                    Some(slots),
+                )
             }
+        };
+        storage_layout.push(StorageMemberLayout {
+            name: name.clone(),
+            ty: type_ast.as_syntax_node().get_text_without_trivia(db),
+            base_address,
+            slots,
+        });
+        let mut patches = UnorderedHashMap::from([
+            (
+                "storage_var_name".to_string(),
+                RewriteNode::new_trimmed(member.name(db).as_syntax_node()),
+            ),
+            ("extra_uses".to_string(), extra_uses_node.clone()),
+            ("value_type".to_string(), value_type),
+        ]);
+        if let Some(key_type) = key_type {
+            patches.insert("key_type".to_string(), key_type);
+        }
+        for (i, component) in tuple_components.iter().flatten().enumerate() {
+            patches.insert(
+                format!("component_type_{i}"),
+                RewriteNode::new_trimmed(component.as_syntax_node()),
+            );
         }
+        vars_code.push(RewriteNode::interpolate_patched(&template, patches));
     }
+    let member_slots = simple_members
+        .iter()
+        .map(|(_, base_address, slots)| (base_address.clone(), *slots))
+        .collect_vec();
+    for (overlapped, overlapping) in find_overlapping_storage_members(&member_slots) {
+        let overlapping_name = &simple_members[overlapping].0;
+        diagnostics.push(PluginDiagnostic {
+            message: format!(
+                "The storage address of '{}' overlaps the storage of '{}'.",
+                overlapping_name.text(db),
+                simple_members[overlapped].0.text(db)
+            ),
+            stable_ptr: overlapping_name.stable_ptr().untyped(),
+        });
+    }
+
     let empty_event_code =
         if has_event { "" } else { "#[event] #[derive(Drop, starknet::Event)] enum Event {}\n" };
     let storage_code = RewriteNode::interpolate_patched(
@@ -118,18 +367,787 @@ pub fn handle_storage_struct(
                         ).unwrap_syscall()
                     }}
                 }}
-            $vars_code$
+            $member_uses_code$
+                mod {STORAGE_MODULE} {{$vars_code$
+                }}
         ",
         )
         .as_str(),
         UnorderedHashMap::from([
             ("members_code".to_string(), RewriteNode::new_modified(members_code)),
             ("vars_code".to_string(), RewriteNode::new_modified(vars_code)),
+            ("member_uses_code".to_string(), RewriteNode::new_modified(member_uses_code)),
             ("member_init_code".to_string(), RewriteNode::new_modified(members_init_code)),
             ("empty_event_code".to_string(), RewriteNode::Text(empty_event_code.to_string())),
         ]),
     );
-    (storage_code, diagnostics)
+    (storage_code, diagnostics, storage_layout)
+}
+
+/// Returns the component types of a tuple type, or None if the type isn't a tuple.
+fn try_extract_tuple_components(
+    db: &dyn SyntaxGroup,
+    type_ast: &ast::Expr,
+) -> Option<Vec<ast::Expr>> {
+    match type_ast {
+        ast::Expr::Parenthesized(expr) => try_extract_tuple_components(db, &expr.expr(db)),
+        ast::Expr::Tuple(tuple) => Some(tuple.expressions(db).elements(db)),
+        _ => None,
+    }
+}
+
+/// Returns the number of storage slots a value of the given type occupies. Types whose size isn't
+/// known syntactically are assumed to occupy the maximal MAX_STORAGE_MEMBER_SLOTS slots.
+fn storage_member_slots(db: &dyn SyntaxGroup, type_ast: &ast::Expr) -> usize {
+    known_storage_member_slots(db, type_ast).unwrap_or(MAX_STORAGE_MEMBER_SLOTS)
+}
+
+/// Returns the number of storage slots a value of the given type occupies, or None if it isn't
+/// known syntactically.
+fn known_storage_member_slots(db: &dyn SyntaxGroup, type_ast: &ast::Expr) -> Option<usize> {
+    match type_ast {
+        ast::Expr::Parenthesized(expr) => known_storage_member_slots(db, &expr.expr(db)),
+        ast::Expr::Tuple(tuple) => tuple
+            .expressions(db)
+            .elements(db)
+            .iter()
+            .map(|element| known_storage_member_slots(db, element))
+            .sum::<Option<usize>>()
+            .map(|slots| slots.min(MAX_STORAGE_MEMBER_SLOTS)),
+        ast::Expr::Path(path) => match path.as_syntax_node().get_text_without_trivia(db).as_str() {
+            "felt252" | "bool" | "u8" | "u16" | "u32" | "u64" | "u128" | "ContractAddress"
+            | "starknet::ContractAddress" | "ClassHash" | "starknet::ClassHash" | "EthAddress"
+            | "starknet::EthAddress" | "StorageAddress" | "starknet::StorageAddress" => Some(1),
+            "u256" => Some(2),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// A storage member of a FLAT_ATTR struct type, or a field of such a type of a FLAT_ATTR struct.
+struct FlatMember<'a> {
+    /// The path of the member from the storage, e.g. `config.limits`.
+    path: String,
+    /// The name of the module of the member inside STORAGE_MODULE, e.g. `config__limits`.
+    module_name: String,
+    /// The felt252 expression of the address the names of the fields are hashed with.
+    address: String,
+    /// The struct type of the member.
+    flat_struct: ast::ItemStruct,
+    /// The names of the FLAT_ATTR structs the member is nested in, to report recursive structs.
+    enclosing_structs: Vec<SmolStr>,
+    inline: &'a str,
+    read_failure: ReadFailure,
+}
+
+/// Generates the modules of a storage member of a FLAT_ATTR struct type: a module whose
+/// ContractState holds the states of the fields, and a module per field, named after the module of
+/// the member and the field. Each field is stored at the Pedersen hash of the address of the member
+/// and the `starknet_keccak` of its name. Returns the expression initializing the ContractState of
+/// the member.
+fn handle_flat_storage_var(
+    db: &dyn SyntaxGroup,
+    diagnostics: &mut Vec<PluginDiagnostic>,
+    contract_structs: &ContractStructs,
+    extra_uses_node: &RewriteNode,
+    member: FlatMember<'_>,
+    vars_code: &mut Vec<RewriteNode>,
+    member_uses_code: &mut Vec<RewriteNode>,
+) -> String {
+    let mut fields_code = vec![];
+    let mut fields_init_code = vec![];
+    let mut enclosing_structs = member.enclosing_structs.clone();
+    enclosing_structs.push(member.flat_struct.name(db).text(db));
+    for field in member.flat_struct.members(db).elements(db) {
+        let type_ast = field.type_clause(db).ty(db);
+        if let ast::Expr::Missing(_) = type_ast {
+            // Already reported by the parser.
+            continue;
+        }
+        if !is_storable_type_expr(db, &type_ast) {
+            diagnostics.push(PluginDiagnostic {
+                message: "Unsupported storage member type.".to_string(),
+                stable_ptr: type_ast.stable_ptr().untyped(),
+            });
+            continue;
+        }
+        if try_extract_mapping_types(db, &type_ast).is_some() {
+            diagnostics.push(PluginDiagnostic {
+                message: format!("Mappings are not supported in `#[{FLAT_ATTR}]` structs."),
+                stable_ptr: type_ast.stable_ptr().untyped(),
+            });
+            continue;
+        }
+        let field_name = field.name(db).text(db);
+        let path = format!("{}.{field_name}", member.path);
+        let module_name = format!("{}__{field_name}", member.module_name);
+        let field_address = format!(
+            "hash::LegacyHash::<felt252>::hash({}, 0x{:x})",
+            member.address,
+            starknet_keccak(field_name.as_bytes())
+        );
+        let init_code = match contract_structs.get(&struct_type_name(db, &type_ast)) {
+            Some(Some(flat_struct)) => {
+                if enclosing_structs.contains(&flat_struct.name(db).text(db)) {
+                    diagnostics.push(PluginDiagnostic {
+                        message: format!("Recursive `#[{FLAT_ATTR}]` structs are not supported."),
+                        stable_ptr: type_ast.stable_ptr().untyped(),
+                    });
+                    continue;
+                }
+                handle_flat_storage_var(
+                    db,
+                    diagnostics,
+                    contract_structs,
+                    extra_uses_node,
+                    FlatMember {
+                        path,
+                        module_name: module_name.clone(),
+                        address: field_address,
+                        flat_struct: flat_struct.clone(),
+                        enclosing_structs: enclosing_structs.clone(),
+                        inline: member.inline,
+                        read_failure: member.read_failure,
+                    },
+                    vars_code,
+                    member_uses_code,
+                )
+            }
+            Some(None) => {
+                diagnostics.push(unsupported_struct_diagnostic(&type_ast));
+                continue;
+            }
+            None => {
+                let template = handle_simple_storage_var(
+                    &path,
+                    &format!(
+                        "the Pedersen hash of the address of `{}` and `0x{:x}`",
+                        member.path,
+                        starknet_keccak(field_name.as_bytes())
+                    ),
+                    &format!("starknet::storage_base_address_from_felt252({field_address})"),
+                    member.inline,
+                    member.read_failure,
+                );
+                vars_code.push(RewriteNode::interpolate_patched(
+                    &template,
+                    UnorderedHashMap::from([
+                        ("storage_var_name".to_string(), RewriteNode::Text(module_name.clone())),
+                        ("extra_uses".to_string(), extra_uses_node.clone()),
+                        (
+                            "value_type".to_string(),
+                            RewriteNode::new_trimmed(type_ast.as_syntax_node()),
+                        ),
+                    ]),
+                ));
+                member_uses_code.push(RewriteNode::Text(format!(
+                    "
+    use {STORAGE_MODULE}::{module_name}::InternalContractStateTrait as \
+                     {module_name}ContractStateTrait;"
+                )));
+                format!("{STORAGE_MODULE}::{module_name}::ContractState{{}}")
+            }
+        };
+        fields_code.push(format!(
+            "
+            {field_name}: super::{module_name}::ContractState,"
+        ));
+        fields_init_code.push(format!("{field_name}: {init_code}"));
+    }
+    vars_code.push(RewriteNode::Text(format!(
+        "
+    mod {} {{
+        #[derive(Copy, Drop)]
+        struct ContractState {{{}
+        }}
+    }}",
+        member.module_name,
+        fields_code.join("")
+    )));
+    format!(
+        "{STORAGE_MODULE}::{}::ContractState{{{}}}",
+        member.module_name,
+        fields_init_code.join(", ")
+    )
+}
+
+/// Returns the name of the struct the type expression may refer to, i.e. its text if it is a path
+/// of a single segment without generic arguments, to be looked up in ContractStructs.
+fn struct_type_name(db: &dyn SyntaxGroup, type_ast: &ast::Expr) -> SmolStr {
+    match type_ast {
+        ast::Expr::Path(path) => match &path.elements(db)[..] {
+            [ast::PathSegment::Simple(segment)] => segment.ident(db).text(db),
+            _ => "".into(),
+        },
+        _ => "".into(),
+    }
+}
+
+/// Returns the diagnostic of a storage member whose type is a struct declared in the contract,
+/// which is neither marked with FLAT_ATTR nor implements `StorageAccess`.
+fn unsupported_struct_diagnostic(type_ast: &ast::Expr) -> PluginDiagnostic {
+    PluginDiagnostic {
+        message: format!(
+            "Storage members of struct types must be `#[{FLAT_ATTR}]` structs, or implement \
+             `StorageAccess`."
+        ),
+        stable_ptr: type_ast.stable_ptr().untyped(),
+    }
+}
+
+/// Returns the paths from the storage of the values stored by the member `path` of the FLAT_ATTR
+/// struct type `flat_struct`, with their types, in declaration order, e.g. `config.owner`.
+/// Unsupported fields, which are already reported, are skipped.
+fn flat_member_values(
+    db: &dyn SyntaxGroup,
+    contract_structs: &ContractStructs,
+    path: &str,
+    flat_struct: &ast::ItemStruct,
+    enclosing_structs: &mut Vec<SmolStr>,
+) -> Vec<(String, ast::Expr)> {
+    let mut values = vec![];
+    enclosing_structs.push(flat_struct.name(db).text(db));
+    for field in flat_struct.members(db).elements(db) {
+        let type_ast = field.type_clause(db).ty(db);
+        if !is_storable_type_expr(db, &type_ast)
+            || try_extract_mapping_types(db, &type_ast).is_some()
+        {
+            continue;
+        }
+        let field_path = format!("{path}.{}", field.name(db).text(db));
+        match contract_structs.get(&struct_type_name(db, &type_ast)) {
+            Some(Some(field_struct)) => {
+                if !enclosing_structs.contains(&field_struct.name(db).text(db)) {
+                    values.extend(flat_member_values(
+                        db,
+                        contract_structs,
+                        &field_path,
+                        field_struct,
+                        enclosing_structs,
+                    ));
+                }
+            }
+            Some(None) => {}
+            None => values.push((field_path, type_ast)),
+        }
+    }
+    enclosing_structs.pop();
+    values
+}
+
+/// Given the base address and slot count of storage members, returns the pairs of indices
+/// `(a, b)` such that the base address of member `b` is within the slots of member `a`.
+/// Members whose slot count isn't known are not checked for being overlapped, as the maximal
+/// count they are assumed to occupy would report overlaps of most members pinned after them.
+pub(super) fn find_overlapping_storage_members(
+    members: &[(BigUint, Option<usize>)],
+) -> Vec<(usize, usize)> {
+    let mut overlaps = vec![];
+    for (a, (base_a, slots_a)) in members.iter().enumerate() {
+        let Some(slots_a) = slots_a else {
+            continue;
+        };
+        for (b, (base_b, _)) in members.iter().enumerate() {
+            if a != b && base_a <= base_b && *base_b < base_a + *slots_a {
+                overlaps.push((a, b));
+            }
+        }
+    }
+    overlaps
+}
+
+/// Generates the STORAGE_GETTER_FUNCTION view, returning the values of all the storage members,
+/// serialized in declaration order. Mapping members are skipped, as their entries can't be
+/// enumerated. Members of FLAT_ATTR struct types are serialized field by field.
+pub fn generate_storage_getter(
+    db: &dyn SyntaxGroup,
+    struct_ast: &ast::ItemStruct,
+    contract_structs: &ContractStructs,
+) -> RewriteNode {
+    let mut serializations = vec![];
+    for member in struct_ast.members(db).elements(db) {
+        let type_ast = member.type_clause(db).ty(db);
+        if try_extract_mapping_types(db, &type_ast).is_some() {
+            continue;
+        }
+        if let Some(Some(flat_struct)) = contract_structs.get(&struct_type_name(db, &type_ast)) {
+            let path = member.name(db).text(db);
+            for (value_path, value_type) in
+                flat_member_values(db, contract_structs, &path, flat_struct, &mut vec![])
+            {
+                serializations.push(RewriteNode::interpolate_patched(
+                    &format!(
+                        "
+    serde::Serde::<$value_type$>::serialize(@self.{value_path}.read(), ref arr);"
+                    ),
+                    UnorderedHashMap::from([(
+                        "value_type".to_string(),
+                        RewriteNode::new_trimmed(value_type.as_syntax_node()),
+                    )]),
+                ));
+            }
+            continue;
+        }
+        serializations.push(RewriteNode::interpolate_patched(
+            "
+    serde::Serde::<$value_type$>::serialize(@self.$name$.read(), ref arr);",
+            UnorderedHashMap::from([
+                ("name".to_string(), RewriteNode::new_trimmed(member.name(db).as_syntax_node())),
+                ("value_type".to_string(), RewriteNode::new_trimmed(type_ast.as_syntax_node())),
+            ]),
+        ));
+    }
+    RewriteNode::interpolate_patched(
+        &formatdoc!(
+            "
+
+            #[{EXTERNAL_ATTR}(v0)]
+            #[{RAW_OUTPUT_ATTR}]
+            fn {STORAGE_GETTER_FUNCTION}(self: @ContractState) -> Span::<felt252> {{
+                let mut arr = array::array_new();$serializations$
+                array::ArrayTrait::span(@arr)
+            }}
+            "
+        ),
+        UnorderedHashMap::from([(
+            "serializations".to_string(),
+            RewriteNode::new_modified(serializations),
+        )]),
+    )
+}
+
+/// Generates the STORAGE_RESET_FUNCTION function, writing the zero value to all the storage
+/// members. Mapping members are skipped, as their entries can't be enumerated, and a note is left
+/// in the generated code instead. Members of FLAT_ATTR struct types are reset field by field.
+pub fn generate_storage_reset(
+    db: &dyn SyntaxGroup,
+    struct_ast: &ast::ItemStruct,
+    contract_structs: &ContractStructs,
+) -> RewriteNode {
+    let mut writes = vec![];
+    for member in struct_ast.members(db).elements(db) {
+        let type_ast = member.type_clause(db).ty(db);
+        if let Some(Some(flat_struct)) = contract_structs.get(&struct_type_name(db, &type_ast)) {
+            let path = member.name(db).text(db);
+            for (value_path, value_type) in
+                flat_member_values(db, contract_structs, &path, flat_struct, &mut vec![])
+            {
+                writes.push(RewriteNode::interpolate_patched(
+                    &format!(
+                        "
+    self.{value_path}.write(zeroable::Zeroable::<$value_type$>::zero());"
+                    ),
+                    UnorderedHashMap::from([(
+                        "value_type".to_string(),
+                        RewriteNode::new_trimmed(value_type.as_syntax_node()),
+                    )]),
+                ));
+            }
+            continue;
+        }
+        let name = RewriteNode::new_trimmed(member.name(db).as_syntax_node());
+        if try_extract_mapping_types(db, &type_ast).is_some() {
+            writes.push(RewriteNode::interpolate_patched(
+                "
+    // Mapping `$name$` is skipped, as its entries can't be enumerated.",
+                UnorderedHashMap::from([("name".to_string(), name)]),
+            ));
+            continue;
+        }
+        writes.push(RewriteNode::interpolate_patched(
+            "
+    self.$name$.write(zeroable::Zeroable::<$value_type$>::zero());",
+            UnorderedHashMap::from([
+                ("name".to_string(), name),
+                ("value_type".to_string(), RewriteNode::new_trimmed(type_ast.as_syntax_node())),
+            ]),
+        ));
+    }
+    RewriteNode::interpolate_patched(
+        &formatdoc!(
+            "
+
+            #[cfg(test)]
+            fn {STORAGE_RESET_FUNCTION}(ref self: ContractState) {{$writes$
+            }}
+            "
+        ),
+        UnorderedHashMap::from([("writes".to_string(), RewriteNode::new_modified(writes))]),
+    )
+}
+
+/// Returns the name given to the MIGRATE_FROM_ATTR attribute, if it is given a single argument.
+pub fn migrated_struct_name(db: &dyn SyntaxGroup, attr: &ast::Attribute) -> Option<String> {
+    let OptionArgListParenthesized::ArgListParenthesized(args) = attr.arguments(db) else {
+        return None;
+    };
+    match &args.args(db).elements(db)[..] {
+        [arg] => Some(arg.as_syntax_node().get_text_without_trivia(db)),
+        _ => None,
+    }
+}
+
+/// Returns the struct declared in the contract that the MIGRATE_FROM_ATTR attribute of the storage
+/// struct refers to, if the attribute is used. Reports the attribute if it doesn't name such a
+/// struct.
+pub fn get_migrated_struct(
+    db: &dyn SyntaxGroup,
+    diagnostics: &mut Vec<PluginDiagnostic>,
+    struct_ast: &ast::ItemStruct,
+    items: &[ast::Item],
+) -> Option<ast::ItemStruct> {
+    let attr = struct_ast.find_attr(db, MIGRATE_FROM_ATTR)?;
+    let name = migrated_struct_name(db, &attr);
+    let migrated_struct = items.iter().find_map(|item| match item {
+        ast::Item::Struct(item_struct)
+            if Some(item_struct.name(db).text(db).as_str()) == name.as_deref()
+                && item_struct.stable_ptr() != struct_ast.stable_ptr() =>
+        {
+            Some(item_struct.clone())
+        }
+        _ => None,
+    });
+    if migrated_struct.is_none() {
+        diagnostics.push(PluginDiagnostic {
+            message: format!(
+                "`{MIGRATE_FROM_ATTR}` expects a single argument, the name of the previous \
+                 version of the storage struct, declared in the contract."
+            ),
+            stable_ptr: attr.stable_ptr().untyped(),
+        });
+    }
+    migrated_struct
+}
+
+/// Generates the STORAGE_MIGRATE_FUNCTION function, copying the values of the members of
+/// `migrated_struct`, a previous version of the storage struct, to the members of the same name
+/// whose address changed, e.g. by pinning them with STORAGE_SLOT_ATTR. Members whose type changed
+/// are reported. Mapping members and members of FLAT_ATTR struct types are skipped, and a note is
+/// left in the generated code instead.
+pub fn generate_storage_migration(
+    db: &dyn SyntaxGroup,
+    diagnostics: &mut Vec<PluginDiagnostic>,
+    struct_ast: &ast::ItemStruct,
+    migrated_struct: &ast::ItemStruct,
+    contract_structs: &ContractStructs,
+) -> RewriteNode {
+    let migrated_members = migrated_struct.members(db).elements(db);
+    let mut copies = vec![];
+    for member in struct_ast.members(db).elements(db) {
+        let name = member.name(db).text(db);
+        let Some(migrated_member) =
+            migrated_members.iter().find(|migrated| migrated.name(db).text(db) == name) else {
+            continue;
+        };
+        let type_ast = member.type_clause(db).ty(db);
+        let type_text = type_ast.as_syntax_node().get_text_without_trivia(db);
+        let migrated_type_text =
+            migrated_member.type_clause(db).ty(db).as_syntax_node().get_text_without_trivia(db);
+        if type_text != migrated_type_text {
+            diagnostics.push(PluginDiagnostic {
+                message: format!(
+                    "The type of '{name}' differs from its type in `{}`, which is not supported \
+                     by `{MIGRATE_FROM_ATTR}`.",
+                    migrated_struct.name(db).text(db)
+                ),
+                stable_ptr: type_ast.stable_ptr().untyped(),
+            });
+            continue;
+        }
+        let name_node = RewriteNode::new_trimmed(member.name(db).as_syntax_node());
+        if try_extract_mapping_types(db, &type_ast).is_some() {
+            copies.push(RewriteNode::interpolate_patched(
+                "
+    // Mapping `$name$` is skipped, as its entries can't be enumerated.",
+                UnorderedHashMap::from([("name".to_string(), name_node)]),
+            ));
+            continue;
+        }
+        if let Some(Some(_)) = contract_structs.get(&struct_type_name(db, &type_ast)) {
+            copies.push(RewriteNode::interpolate_patched(
+                &format!(
+                    "
+    // Member `$name$` is skipped, as its type is a `#[{FLAT_ATTR}]` struct."
+                ),
+                UnorderedHashMap::from([("name".to_string(), name_node)]),
+            ));
+            continue;
+        }
+        let migrated_address = get_storage_slot(db, diagnostics, migrated_member)
+            .unwrap_or_else(|| starknet_keccak(name.as_bytes()));
+        // The attributes of the members of the storage struct are already reported.
+        let address = get_storage_slot(db, &mut vec![], &member)
+            .unwrap_or_else(|| starknet_keccak(name.as_bytes()));
+        if address == migrated_address {
+            continue;
+        }
+        copies.push(RewriteNode::interpolate_patched(
+            &format!(
+                "
+    self.$name$.write(
+        starknet::StorageAccess::<$value_type$>::read(
+            0_u32, starknet::storage_base_address_const::<0x{migrated_address:x}>()
+        ).unwrap_syscall()
+    );"
+            ),
+            UnorderedHashMap::from([
+                ("name".to_string(), name_node),
+                ("value_type".to_string(), RewriteNode::new_trimmed(type_ast.as_syntax_node())),
+            ]),
+        ));
+    }
+    RewriteNode::interpolate_patched(
+        &formatdoc!(
+            "
+
+            fn {STORAGE_MIGRATE_FUNCTION}(ref self: ContractState) {{$copies$
+            }}
+            "
+        ),
+        UnorderedHashMap::from([("copies".to_string(), RewriteNode::new_modified(copies))]),
+    )
+}
+
+/// Generates the view of a storage member annotated with GETTER_ATTR, returning the value of the
+/// member. Mapping members are reported, as they can't be read without a key, and so are members
+/// of FLAT_ATTR struct types, which are read field by field.
+pub fn generate_member_getter(
+    db: &dyn SyntaxGroup,
+    diagnostics: &mut Vec<PluginDiagnostic>,
+    member: &ast::Member,
+    contract_structs: &ContractStructs,
+) -> Option<RewriteNode> {
+    let type_ast = member.type_clause(db).ty(db);
+    if try_extract_mapping_types(db, &type_ast).is_some() {
+        diagnostics.push(PluginDiagnostic {
+            message: format!("The '{GETTER_ATTR}' attribute is not supported for mappings."),
+            stable_ptr: type_ast.stable_ptr().untyped(),
+        });
+        return None;
+    }
+    if let Some(Some(_)) = contract_structs.get(&struct_type_name(db, &type_ast)) {
+        diagnostics.push(PluginDiagnostic {
+            message: format!(
+                "The '{GETTER_ATTR}' attribute is not supported for `#[{FLAT_ATTR}]` members."
+            ),
+            stable_ptr: type_ast.stable_ptr().untyped(),
+        });
+        return None;
+    }
+    Some(RewriteNode::interpolate_patched(
+        &formatdoc!(
+            "
+
+            #[{EXTERNAL_ATTR}(v0)]
+            fn {GETTER_FUNCTION_PREFIX}$name$(self: @ContractState) -> $value_type$ {{
+                self.$name$.read()
+            }}
+            "
+        ),
+        UnorderedHashMap::from([
+            ("name".to_string(), RewriteNode::new_trimmed(member.name(db).as_syntax_node())),
+            ("value_type".to_string(), RewriteNode::new_trimmed(type_ast.as_syntax_node())),
+        ]),
+    ))
+}
+
+/// Returns whether the type expression is of a form that can be stored, i.e. a path to a (possibly
+/// generic) type, or a tuple of such types. Other expressions, such as snapshots, are rejected.
+fn is_storable_type_expr(db: &dyn SyntaxGroup, type_ast: &ast::Expr) -> bool {
+    match type_ast {
+        ast::Expr::Path(_) => true,
+        ast::Expr::Parenthesized(expr) => is_storable_type_expr(db, &expr.expr(db)),
+        ast::Expr::Tuple(tuple) => tuple
+            .expressions(db)
+            .elements(db)
+            .iter()
+            .all(|element| is_storable_type_expr(db, element)),
+        _ => false,
+    }
+}
+
+/// The function hashing the keys of a mapping into storage addresses.
+#[derive(Clone, Copy)]
+enum KeyHash {
+    /// Hashing with `hash::LegacyHash`, based on Pedersen.
+    Pedersen,
+    /// Hashing the address and the `Serde` serialization of the key with Poseidon.
+    Poseidon,
+}
+
+/// Returns the key hash selected by the HASH_ATTR attribute of the member, defaulting to Pedersen.
+/// Reports the attribute if it is used on a non mapping member or with an unknown hash name.
+fn get_key_hash(
+    db: &dyn SyntaxGroup,
+    diagnostics: &mut Vec<PluginDiagnostic>,
+    member: &ast::Member,
+    is_mapping: bool,
+) -> KeyHash {
+    let Some(attr) = member.find_attr(db, HASH_ATTR) else {
+        return KeyHash::Pedersen;
+    };
+    if !is_mapping {
+        diagnostics.push(PluginDiagnostic {
+            message: format!("The '{HASH_ATTR}' attribute is only supported for mappings."),
+            stable_ptr: attr.stable_ptr().untyped(),
+        });
+        return KeyHash::Pedersen;
+    }
+    let name = match attr.arguments(db) {
+        OptionArgListParenthesized::ArgListParenthesized(args) => {
+            match &args.args(db).elements(db)[..] {
+                [arg] => Some(arg.as_syntax_node().get_text_without_trivia(db)),
+                _ => None,
+            }
+        }
+        OptionArgListParenthesized::Empty(_) => None,
+    };
+    match name.as_deref() {
+        Some("pedersen") => KeyHash::Pedersen,
+        Some("poseidon") => KeyHash::Poseidon,
+        _ => {
+            diagnostics.push(PluginDiagnostic {
+                message: format!(
+                    "`{HASH_ATTR}` expects a single hash name argument, either `pedersen` or \
+                     `poseidon`."
+                ),
+                stable_ptr: attr.stable_ptr().untyped(),
+            });
+            KeyHash::Pedersen
+        }
+    }
+}
+
+/// Returns whether the member is annotated with OPTIONAL_READS_ATTR. Reports the attribute if it is
+/// used on a non mapping member, as only the absent entries of mappings read as zero.
+fn has_optional_reads(
+    db: &dyn SyntaxGroup,
+    diagnostics: &mut Vec<PluginDiagnostic>,
+    member: &ast::Member,
+    is_mapping: bool,
+) -> bool {
+    let Some(attr) = member.find_attr(db, OPTIONAL_READS_ATTR) else {
+        return false;
+    };
+    if !is_mapping {
+        diagnostics.push(PluginDiagnostic {
+            message: format!(
+                "The '{OPTIONAL_READS_ATTR}' attribute is only supported for mappings. Storage \
+                 doesn't track presence, so absent mapping entries are told apart by a zero \
+                 first storage slot."
+            ),
+            stable_ptr: attr.stable_ptr().untyped(),
+        });
+        return false;
+    }
+    true
+}
+
+/// Returns the storage slot the member is pinned to by STORAGE_SLOT_ATTR, if any. The slot must be
+/// a valid storage base address, i.e. below `2**251 - 256`.
+fn get_storage_slot(
+    db: &dyn SyntaxGroup,
+    diagnostics: &mut Vec<PluginDiagnostic>,
+    member: &ast::Member,
+) -> Option<BigUint> {
+    let attr = member.find_attr(db, STORAGE_SLOT_ATTR)?;
+    let slot = match attr.arguments(db) {
+        OptionArgListParenthesized::ArgListParenthesized(args) => {
+            match &args.args(db).elements(db)[..] {
+                [arg] => try_extract_felt252_literal(db, arg).and_then(|slot| slot.to_biguint()),
+                _ => None,
+            }
+        }
+        OptionArgListParenthesized::Empty(_) => None,
+    };
+    let max_slot = (BigUint::from(1_u32) << 251) - BigUint::from(256_u32);
+    match slot {
+        Some(slot) if slot < max_slot => Some(slot),
+        _ => {
+            diagnostics.push(PluginDiagnostic {
+                message: format!(
+                    "`{STORAGE_SLOT_ATTR}` expects a single storage address argument, which must \
+                     be a felt252 literal below 2**251 - 256."
+                ),
+                stable_ptr: attr.stable_ptr().untyped(),
+            });
+            None
+        }
+    }
+}
+
+/// The handling of failed storage reads.
+#[derive(Clone, Copy)]
+enum ReadFailure {
+    /// Panicking with the revert reason of the syscall.
+    Unwrap,
+    /// Panicking with the revert reason of the syscall, followed by a failed storage read marker.
+    Panic,
+}
+
+/// Returns the read failure handling selected by the READ_FAILURE_ATTR attribute of the member,
+/// defaulting to unwrapping. Reports the attribute if it is used with an unknown option.
+fn get_read_failure(
+    db: &dyn SyntaxGroup,
+    diagnostics: &mut Vec<PluginDiagnostic>,
+    member: &ast::Member,
+) -> ReadFailure {
+    let Some(attr) = member.find_attr(db, READ_FAILURE_ATTR) else {
+        return ReadFailure::Unwrap;
+    };
+    let option = match attr.arguments(db) {
+        OptionArgListParenthesized::ArgListParenthesized(args) => {
+            match &args.args(db).elements(db)[..] {
+                [arg] => Some(arg.as_syntax_node().get_text_without_trivia(db)),
+                _ => None,
+            }
+        }
+        OptionArgListParenthesized::Empty(_) => None,
+    };
+    match option.as_deref() {
+        Some("unwrap") => ReadFailure::Unwrap,
+        Some("panic") => ReadFailure::Panic,
+        _ => {
+            diagnostics.push(PluginDiagnostic {
+                message: format!(
+                    "`{READ_FAILURE_ATTR}` expects a single option argument, either `unwrap` or \
+                     `panic`."
+                ),
+                stable_ptr: attr.stable_ptr().untyped(),
+            });
+            ReadFailure::Unwrap
+        }
+    }
+}
+
+/// Returns the code of a storage read of the value at `address_code`, handling failures according
+/// to `read_failure`.
+fn storage_read_code(address_code: &str, read_failure: ReadFailure) -> String {
+    let read_call = format!(
+        "starknet::StorageAccess::<$value_type$>::read(
+                    address_domain,
+                    {address_code},
+                )"
+    );
+    handle_read_failure(read_call, read_failure)
+}
+
+/// Returns the code of `read_call`, a storage read returning a `SyscallResult`, handling failures
+/// according to `read_failure`.
+fn handle_read_failure(read_call: String, read_failure: ReadFailure) -> String {
+    match read_failure {
+        ReadFailure::Unwrap => format!("{read_call}.unwrap_syscall()"),
+        ReadFailure::Panic => format!(
+            "match {read_call} {{
+                    Result::Ok(value) => value,
+                    Result::Err(mut revert_reason) => {{
+                        array::array_append(ref revert_reason, 'Storage read failed');
+                        panic(revert_reason)
+                    }},
+                }}"
+        ),
+    }
 }
 
 /// The type of the mapping storage variable.
@@ -166,40 +1184,85 @@ fn try_extract_mapping_types(
     }
 }
 
-/// Generate getters and setters skeleton for a non-mapping member in the storage struct.
-fn handle_simple_storage_var(address: &str) -> String {
+/// The accessors of a storage member, documented in [accessors_declaration].
+enum StorageVarAccessors<'a> {
+    /// The accessors of the non mapping member `name`.
+    Simple { name: &'a str },
+    /// The accessors of the entries of the mapping member `name`, given their `key`.
+    Mapping { name: &'a str },
+}
+
+/// Returns the beginning of the module of a storage member, up to the declaration of its
+/// accessors trait, shared by all the kinds of members. `trait_doc` is the doc comment of the
+/// trait, and `extra_declarations` are the declarations of the accessors specific to the member.
+fn accessors_declaration(
+    trait_doc: &str,
+    accessors: &StorageVarAccessors<'_>,
+    extra_declarations: &str,
+) -> String {
+    let (key_param, address_of, value_of) = match accessors {
+        StorageVarAccessors::Simple { name } => {
+            ("", format!("`{name}`"), format!("the value of `{name}`"))
+        }
+        StorageVarAccessors::Mapping { name } => (
+            ", key: $key_type$",
+            format!("the `{name}` entry of `key`"),
+            format!("the `{name}` entry of `key`"),
+        ),
+    };
     format!(
-        "
-    use $storage_var_name$::InternalContractStateTrait as $storage_var_name$ContractStateTrait;
-    mod $storage_var_name$ {{$extra_uses$
+        "$extra_uses$
         use starknet::SyscallResultTrait;
         use starknet::SyscallResultTraitImpl;
         use super;
 
         #[derive(Copy, Drop)]
         struct ContractState {{}}
+        {trait_doc}
         trait InternalContractStateTrait {{
-            fn address(self: @ContractState) -> starknet::StorageBaseAddress;
-            fn read(self: @ContractState) -> $type_name$;
-            fn write(ref self: ContractState, value: $type_name$);
-        }}
+            /// Returns the storage address of {address_of}.
+            fn address(self: @ContractState{key_param}) -> starknet::StorageBaseAddress;
+            /// Reads {value_of} from storage.
+            fn read(self: @ContractState{key_param}) -> $value_type$;
+            /// Writes `value` to {address_of} in storage.
+            fn write(ref self: ContractState{key_param}, value: $value_type$);{extra_declarations}
+        }}"
+    )
+}
+
+/// Generate getters and setters skeleton for a non-mapping member in the storage struct.
+/// `address_code` is the expression of its storage base address, described by
+/// `address_description` in the docs.
+fn handle_simple_storage_var(
+    name: &str,
+    address_description: &str,
+    address_code: &str,
+    inline: &str,
+    read_failure: ReadFailure,
+) -> String {
+    let read_code = storage_read_code("self.address()", read_failure);
+    let accessors_declaration = accessors_declaration(
+        &format!("/// Accessors of the `{name}` storage member, stored at {address_description}."),
+        &StorageVarAccessors::Simple { name },
+        "",
+    );
+    format!(
+        "
+    mod $storage_var_name$ {{{accessors_declaration}
 
         impl InternalContractStateImpl of InternalContractStateTrait {{
-            fn address(self: @ContractState) -> starknet::StorageBaseAddress {{
-                starknet::storage_base_address_const::<{address}>()
+            {inline}fn address(self: @ContractState) -> starknet::StorageBaseAddress {{
+                {address_code}
             }}
-            fn read(self: @ContractState) -> $type_name$ {{
+            {inline}fn read(self: @ContractState) -> $value_type$ {{
                 // Only address_domain 0 is currently supported.
                 let address_domain = 0_u32;
-                starknet::StorageAccess::<$type_name$>::read(
-                    address_domain,
-                    self.address(),
-                ).unwrap_syscall()
+                {read_code}
             }}
-            fn write(ref self: ContractState, value: $type_name$) {{
+            {inline}fn write(ref self: ContractState, value: $value_type$) {{
                 // Only address_domain 0 is currently supported.
                 let address_domain = 0_u32;
-                starknet::StorageAccess::<$type_name$>::write(
+                starknet::StorageAccess::<$value_type$>::write(
                     address_domain,
                     self.address(),
                     value,
@@ -210,38 +1273,166 @@ fn handle_simple_storage_var(address: &str) -> String {
     )
 }
 
-/// Generate getters and setters skeleton for a non-mapping member in the storage struct.
-fn handle_legacy_mapping_storage_var(address: &str) -> String {
+/// Generate getters and setters skeleton for a non-mapping member of a tuple type with
+/// `components` components, each occupying a single slot. Component `i` is stored at offset `i`
+/// from the storage base address of the member, see `handle_simple_storage_var`. The type of
+/// component `i` is patched as `$component_type_i$`.
+fn handle_tuple_storage_var(
+    name: &str,
+    address_description: &str,
+    address_code: &str,
+    inline: &str,
+    read_failure: ReadFailure,
+    components: usize,
+) -> String {
+    let reads = (0..components)
+        .map(|i| {
+            let read_code = handle_read_failure(
+                format!(
+                    "starknet::StorageAccess::<$component_type_{i}$>::read_at_offset_internal(
+                    address_domain,
+                    base,
+                    {i}_u8,
+                )"
+                ),
+                read_failure,
+            );
+            format!(
+                "
+                let value_{i} = {read_code};"
+            )
+        })
+        .join("");
+    let writes = (0..components)
+        .map(|i| {
+            format!(
+                "
+                starknet::StorageAccess::<$component_type_{i}$>::write_at_offset_internal(
+                    address_domain,
+                    base,
+                    {i}_u8,
+                    value_{i},
+                ).unwrap_syscall();"
+            )
+        })
+        .join("");
+    let accessors_declaration = accessors_declaration(
+        &format!("/// Accessors of the `{name}` storage member, stored at {address_description}."),
+        &StorageVarAccessors::Simple { name },
+        "",
+    );
+    let mut values = (0..components).map(|i| format!("value_{i}")).join(", ");
+    if components == 1 {
+        values.push(',');
+    }
     format!(
         "
-    use $storage_var_name$::InternalContractStateTrait as $storage_var_name$ContractStateTrait;
-    mod $storage_var_name$ {{$extra_uses$
-        use starknet::SyscallResultTrait;
-        use starknet::SyscallResultTraitImpl;
-        use super;
+    mod $storage_var_name$ {{{accessors_declaration}
 
-        #[derive(Copy, Drop)]
-        struct ContractState {{}}
-        trait InternalContractStateTrait {{
-            fn address(self: @ContractState, key: $key_type$) -> starknet::StorageBaseAddress;
-            fn read(self: @ContractState, key: $key_type$) -> $value_type$;
-            fn write(ref self: ContractState, key: $key_type$, value: $value_type$);
+        impl InternalContractStateImpl of InternalContractStateTrait {{
+            {inline}fn address(self: @ContractState) -> starknet::StorageBaseAddress {{
+                {address_code}
+            }}
+            {inline}fn read(self: @ContractState) -> $value_type$ {{
+                // Only address_domain 0 is currently supported.
+                let address_domain = 0_u32;
+                let base = self.address();{reads}
+                ({values})
+            }}
+            {inline}fn write(ref self: ContractState, value: $value_type$) {{
+                // Only address_domain 0 is currently supported.
+                let address_domain = 0_u32;
+                let base = self.address();
+                let ({values}) = value;{writes}
+            }}
         }}
+    }}"
+    )
+}
 
-        impl InternalContractStateImpl of InternalContractStateTrait {{
-            fn address(self: @ContractState, key: $key_type$) -> starknet::StorageBaseAddress {{
+/// Generate getters and setters skeleton for a `LegacyMap` member in the storage struct. If
+/// `optional_reads` is true, a `read_optional` accessor is generated as well, see
+/// OPTIONAL_READS_ATTR.
+fn handle_legacy_mapping_storage_var(
+    name: &str,
+    address: &str,
+    inline: &str,
+    key_hash: KeyHash,
+    read_failure: ReadFailure,
+    optional_reads: bool,
+) -> String {
+    let read_code = storage_read_code("self.address(key)", read_failure);
+    let (optional_read_declaration, optional_read_impl) = if optional_reads {
+        (
+            format!(
+                "
+            /// Reads the `{name}` entry of `key` from storage, or returns `None` if its first
+            /// storage slot is zero. Entries written with a zero value also read as `None`.
+            fn read_optional(self: @ContractState, key: $key_type$) -> Option<$value_type$>;"
+            ),
+            format!(
+                "
+            {inline}fn read_optional(self: @ContractState, key: $key_type$) -> Option<$value_type$> {{
+                // Only address_domain 0 is currently supported.
+                let address_domain = 0_u32;
+                let base = self.address(key);
+                let first_slot = starknet::syscalls::storage_read_syscall(
+                    address_domain,
+                    starknet::storage_address_from_base(base),
+                ).unwrap_syscall();
+                if first_slot == 0 {{
+                    return Option::None(());
+                }}
+                Option::Some({})
+            }}",
+                storage_read_code("base", read_failure)
+            ),
+        )
+    } else {
+        (String::new(), String::new())
+    };
+    let (hash_name, address_code) = match key_hash {
+        KeyHash::Pedersen => (
+            "Pedersen",
+            format!(
+                "starknet::storage_base_address_from_felt252(
+                    hash::LegacyHash::<$key_type$>::hash({address}, key))"
+            ),
+        ),
+        KeyHash::Poseidon => (
+            "Poseidon",
+            format!(
+                "let mut key_data = array::array_new();
+                array::array_append(ref key_data, {address});
+                serde::Serde::<$key_type$>::serialize(@key, ref key_data);
                 starknet::storage_base_address_from_felt252(
-                    hash::LegacyHash::<$key_type$>::hash({address}, key))
+                    poseidon::poseidon_hash_span(array::ArrayTrait::span(@key_data)))"
+            ),
+        ),
+    };
+    let accessors_declaration = accessors_declaration(
+        &format!(
+            "/// Accessors of the `{name}` storage mapping, whose entries are stored at the \
+             {hash_name}
+        /// hash of `{address}` and the key."
+        ),
+        &StorageVarAccessors::Mapping { name },
+        &optional_read_declaration,
+    );
+    format!(
+        "
+    mod $storage_var_name$ {{{accessors_declaration}
+
+        impl InternalContractStateImpl of InternalContractStateTrait {{
+            {inline}fn address(self: @ContractState, key: $key_type$) -> starknet::StorageBaseAddress {{
+                {address_code}
             }}
-            fn read(self: @ContractState, key: $key_type$) -> $value_type$ {{
+            {inline}fn read(self: @ContractState, key: $key_type$) -> $value_type$ {{
                 // Only address_domain 0 is currently supported.
                 let address_domain = 0_u32;
-                starknet::StorageAccess::<$value_type$>::read(
-                    address_domain,
-                    self.address(key),
-                ).unwrap_syscall()
+                {read_code}
             }}
-            fn write(ref self: ContractState, key: $key_type$, value: $value_type$) {{
+            {inline}fn write(ref self: ContractState, key: $key_type$, value: $value_type$) {{
                 // Only address_domain 0 is currently supported.
                 let address_domain = 0_u32;
                 starknet::StorageAccess::<$value_type$>::write(
@@ -249,7 +1440,7 @@ fn handle_legacy_mapping_storage_var(address: &str) -> String {
                     self.address(key),
                     value,
                 ).unwrap_syscall()
-            }}
+            }}{optional_read_impl}
         }}
     }}"
     )
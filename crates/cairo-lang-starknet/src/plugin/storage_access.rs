@@ -165,6 +165,116 @@ pub fn handle_struct(db: &dyn SyntaxGroup, struct_ast: ast::ItemStruct) -> Plugi
     }
 }
 
+/// Derive the `StorageAccess` trait for enums annotated with `derive(starknet::StorageAccess)`.
+/// The index of the variant is stored at the base address, and its payload in the following
+/// addresses. Variants without a payload, i.e. with a `()` type, only take the index slot. As
+/// storage is zero-initialized, an unwritten enum reads as its first variant.
+pub fn handle_enum(db: &dyn SyntaxGroup, enum_ast: ast::ItemEnum) -> PluginResult {
+    let enum_name = enum_ast.name(db).as_syntax_node().get_text_without_trivia(db);
+    let mut reads = Vec::new();
+    let mut writes = Vec::new();
+    let mut sizes = Vec::new();
+
+    for (i, variant) in enum_ast.variants(db).elements(db).iter().enumerate() {
+        let variant_name = variant.name(db).as_syntax_node().get_text_without_trivia(db);
+        let variant_type_ast = variant.type_clause(db).ty(db);
+        let variant_type = variant_type_ast.as_syntax_node().get_text_without_trivia(db);
+
+        if is_unit_type(db, &variant_type_ast) {
+            reads.push(format!(
+                "if variant == {i} {{
+            return starknet::SyscallResult::Ok({enum_name}::{variant_name}(()));
+        }}"
+            ));
+            writes.push(format!(
+                "{enum_name}::{variant_name}(()) => {{
+                starknet::StorageAccess::<felt252>::write_at_offset_internal(address_domain, \
+                 base, offset, {i})
+            }},"
+            ));
+            sizes.push(format!("{enum_name}::{variant_name}(()) => 1_u8,"));
+        } else {
+            reads.push(format!(
+                "if variant == {i} {{
+            return starknet::SyscallResult::Ok(
+                {enum_name}::{variant_name}(
+                    starknet::StorageAccess::<{variant_type}>::read_at_offset_internal(\
+                 address_domain, base, offset + 1_u8)?
+                )
+            );
+        }}"
+            ));
+            writes.push(format!(
+                "{enum_name}::{variant_name}(payload) => {{
+                starknet::StorageAccess::<felt252>::write_at_offset_internal(address_domain, \
+                 base, offset, {i})?;
+                starknet::StorageAccess::<{variant_type}>::write_at_offset_internal(\
+                 address_domain, base, offset + 1_u8, payload)
+            }},"
+            ));
+            sizes.push(format!(
+                "{enum_name}::{variant_name}(payload) => 1_u8 + \
+                 starknet::StorageAccess::<{variant_type}>::size_internal(payload),"
+            ));
+        }
+    }
+
+    let sa_impl = formatdoc!(
+        "
+        impl StorageAccess{enum_name} of starknet::StorageAccess::<{enum_name}> {{
+            fn read(address_domain: u32, base: starknet::StorageBaseAddress) -> \
+         starknet::SyscallResult<{enum_name}> {{
+                starknet::StorageAccess::<{enum_name}>::read_at_offset_internal(address_domain, \
+         base, 0_u8)
+            }}
+            fn write(address_domain: u32, base: starknet::StorageBaseAddress, value: \
+         {enum_name}) -> starknet::SyscallResult<()> {{
+                starknet::StorageAccess::<{enum_name}>::write_at_offset_internal(address_domain, \
+         base, 0_u8, value)
+            }}
+            fn read_at_offset_internal(address_domain: u32, base: starknet::StorageBaseAddress, \
+         offset: u8) -> starknet::SyscallResult<{enum_name}> {{
+                let variant = starknet::StorageAccess::<felt252>::read_at_offset_internal(\
+         address_domain, base, offset)?;
+                {reads}
+                let mut message = array::ArrayTrait::new();
+                array::ArrayTrait::append(ref message, 'Unknown enum variant');
+                starknet::SyscallResult::Err(message)
+            }}
+            fn write_at_offset_internal(address_domain: u32, base: starknet::StorageBaseAddress, \
+         offset: u8, value: {enum_name}) -> starknet::SyscallResult<()> {{
+                match value {{
+                    {writes}
+                }}
+            }}
+            #[inline(always)]
+            fn size_internal(value: {enum_name}) -> u8 {{
+                match value {{
+                    {sizes}
+                }}
+            }}
+        }}",
+        reads = reads.join("\n        "),
+        writes = writes.join("\n            "),
+        sizes = sizes.join("\n            "),
+    );
+
+    PluginResult {
+        code: Some(PluginGeneratedFile {
+            name: "storage_access_impl".into(),
+            content: sa_impl,
+            aux_data: DynGeneratedFileAuxData(Arc::new(TrivialPluginAuxData {})),
+        }),
+        diagnostics: vec![],
+        remove_original_item: false,
+    }
+}
+
+/// Returns true if the type is the unit type `()`.
+fn is_unit_type(db: &dyn SyntaxGroup, type_ast: &ast::Expr) -> bool {
+    matches!(type_ast, ast::Expr::Tuple(tuple) if tuple.expressions(db).elements(db).is_empty())
+}
+
 /// Returns true if the type should be derived as a storage_access.
 pub fn derive_storage_access_needed<T: QueryAttrs>(with_attrs: &T, db: &dyn SyntaxGroup) -> bool {
     with_attrs.query_attr(db, "derive").into_iter().any(|attr| {
@@ -3,66 +3,181 @@ use std::sync::Arc;
 use cairo_lang_compiler::db::RootDatabase;
 use cairo_lang_compiler::diagnostics::get_diagnostics_as_string;
 use cairo_lang_defs::db::DefsGroup;
-use cairo_lang_defs::ids::ModuleId;
+use cairo_lang_defs::ids::{FunctionWithBodyId, ModuleId, ModuleItemId};
 use cairo_lang_filesystem::db::FilesGroup;
-use cairo_lang_semantic::test_utils::setup_test_module;
+use cairo_lang_filesystem::ids::FileId;
+use cairo_lang_parser::db::ParserGroup;
+use cairo_lang_semantic::db::SemanticGroup;
+use cairo_lang_semantic::items::functions::GenericFunctionId;
+use cairo_lang_semantic::test_utils::{setup_test_crate, setup_test_module};
+use cairo_lang_semantic::{Expr, GenericArgumentId};
+use cairo_lang_syntax::node::{ast, Terminal, TypedSyntaxNode};
 use cairo_lang_test_utils::parse_test_file::TestFileRunner;
 use cairo_lang_utils::ordered_hash_map::OrderedHashMap;
+use cairo_lang_utils::{extract_matches, try_extract_matches};
+use indoc::{formatdoc, indoc};
+use itertools::Itertools;
+use num_bigint::BigUint;
+use test_case::test_case;
 
+use crate::abi::{AbiBuilder, Item};
+use crate::contract::find_contracts;
+use crate::plugin::consts::STORAGE_MODULE;
+use crate::plugin::storage::find_overlapping_storage_members;
 use crate::plugin::StarkNetPlugin;
 
+/// Expands the contracts with the default plugin, or with the plugin configured by the optional
+/// `plugin_options` input, see [plugin_from_options].
 struct ExpandContractTestRunner {
     db: RootDatabase,
 }
 
 impl Default for ExpandContractTestRunner {
     fn default() -> Self {
-        Self {
-            db: RootDatabase::builder()
-                .detect_corelib()
-                .with_semantic_plugin(Arc::new(StarkNetPlugin::default()))
-                .build()
-                .unwrap(),
-        }
+        Self { db: plugin_test_db(StarkNetPlugin::default()) }
     }
 }
 
 impl TestFileRunner for ExpandContractTestRunner {
     fn run(&mut self, inputs: &OrderedHashMap<String, String>) -> OrderedHashMap<String, String> {
+        let mut configured_db;
+        let db = match inputs.get("plugin_options") {
+            Some(options) => {
+                configured_db = plugin_test_db(plugin_from_options(options));
+                &mut configured_db
+            }
+            None => &mut self.db,
+        };
         let (test_module, _semantic_diagnostics) =
-            setup_test_module(&mut self.db, inputs["cairo_code"].as_str()).split();
+            setup_test_module(db, inputs["cairo_code"].as_str()).split();
 
-        let mut module_ids = vec![test_module.module_id];
-        module_ids.extend(
-            self.db
-                .module_submodules_ids(test_module.module_id)
-                .unwrap_or_default()
-                .into_iter()
-                .map(ModuleId::Submodule),
-        );
-        let mut files = vec![];
-        for module_id in module_ids {
-            for file in self.db.module_files(module_id).unwrap_or_default() {
-                if files.contains(&file) {
-                    continue;
-                }
-                files.push(file);
+        let mut file_contents = vec![];
+        for file in module_and_submodule_files(db, test_module.module_id) {
+            file_contents.push(format!("{}:", file.file_name(db)));
+            file_contents.push(db.file_content(file).unwrap().as_ref().clone());
+        }
+
+        OrderedHashMap::from([
+            ("generated_cairo_code".into(), file_contents.join("\n\n")),
+            ("expected_diagnostics".into(), get_diagnostics_as_string(db)),
+        ])
+    }
+}
+
+/// Returns a database with the corelib and the given plugin.
+fn plugin_test_db(plugin: StarkNetPlugin) -> RootDatabase {
+    RootDatabase::builder().detect_corelib().with_semantic_plugin(Arc::new(plugin)).build().unwrap()
+}
+
+/// Returns the plugin configured by the `plugin_options` input of a test. Each line names a method
+/// of [crate::plugin::StarkNetPluginBuilder] without its `with_` prefix, followed by its argument
+/// if it takes one, e.g. `max_entry_point_params: 2`.
+fn plugin_from_options(options: &str) -> StarkNetPlugin {
+    let mut builder = StarkNetPlugin::builder();
+    for option in options.lines() {
+        let (name, arg) = match option.split_once(':') {
+            Some((name, arg)) => (name.trim(), Some(arg.trim())),
+            None => (option.trim(), None),
+        };
+        let usize_arg = || {
+            arg.and_then(|arg| arg.parse().ok())
+                .unwrap_or_else(|| panic!("Plugin option `{name}` requires a numeric argument."))
+        };
+        builder = match name {
+            "max_entry_point_params" => builder.with_max_entry_point_params(usize_arg()),
+            "missing_constructor_lint" => builder.with_missing_constructor_lint(),
+            "default_constructor" => builder.with_default_constructor(),
+            "felt252_panics" => builder.with_felt252_panics(),
+            "minimal_wrappers" => builder.with_minimal_wrappers(),
+            "max_generated_contract_size" => {
+                builder.with_max_generated_contract_size(usize_arg())
+            }
+            _ => panic!("Unknown plugin option `{name}`."),
+        };
+    }
+    builder.build()
+}
+
+/// Returns the files of the given module and of its direct submodules, including the virtual
+/// files generated by plugins, without duplicates.
+fn module_and_submodule_files(db: &RootDatabase, module_id: ModuleId) -> Vec<FileId> {
+    let mut module_ids = vec![module_id];
+    module_ids.extend(
+        db.module_submodules_ids(module_id)
+            .unwrap_or_default()
+            .into_iter()
+            .map(ModuleId::Submodule),
+    );
+    let mut files = vec![];
+    for module_id in module_ids {
+        for file in db.module_files(module_id).unwrap_or_default() {
+            if files.contains(&file) {
+                continue;
             }
+            files.push(file);
         }
-        let mut file_contents = vec![];
+    }
+    files
+}
+
+/// Expands the contracts and reports the size in bytes of each generated entry point wrapper, so
+/// that changes in the verbosity of the generated code show up in the expected outputs.
+struct WrapperSizesTestRunner {
+    db: RootDatabase,
+}
+
+impl Default for WrapperSizesTestRunner {
+    fn default() -> Self {
+        Self { db: plugin_test_db(StarkNetPlugin::default()) }
+    }
+}
+
+impl TestFileRunner for WrapperSizesTestRunner {
+    fn run(&mut self, inputs: &OrderedHashMap<String, String>) -> OrderedHashMap<String, String> {
+        let (test_module, _semantic_diagnostics) =
+            setup_test_module(&mut self.db, inputs["cairo_code"].as_str()).split();
 
-        for file in files {
-            file_contents.push(format!("{}:", file.file_name(&self.db)));
-            file_contents.push(self.db.file_content(file).unwrap().as_ref().clone());
+        let mut sizes = vec![];
+        let mut total_size = 0;
+        for file in module_and_submodule_files(&self.db, test_module.module_id) {
+            if file.file_name(&self.db) != "contract" {
+                continue;
+            }
+            let syntax_file = self.db.file_syntax(file).unwrap();
+            // The wrappers are the free functions of the modules generated for the entry points.
+            for item in syntax_file.items(&self.db).elements(&self.db) {
+                let ast::Item::Module(module) = item else { continue; };
+                let ast::MaybeModuleBody::Some(body) = module.body(&self.db) else { continue; };
+                for item in body.items(&self.db).elements(&self.db) {
+                    let ast::Item::FreeFunction(function) = item else { continue; };
+                    let size = function.as_syntax_node().get_text_without_trivia(&self.db).len();
+                    total_size += size;
+                    sizes.push(format!(
+                        "{}::{}: {size}",
+                        module.name(&self.db).text(&self.db),
+                        function.declaration(&self.db).name(&self.db).text(&self.db)
+                    ));
+                }
+            }
         }
+        sizes.push(format!("total: {total_size}"));
 
         OrderedHashMap::from([
-            ("generated_cairo_code".into(), file_contents.join("\n\n")),
+            ("wrapper_sizes".into(), sizes.join("\n")),
             ("expected_diagnostics".into(), get_diagnostics_as_string(&mut self.db)),
         ])
     }
 }
 
+cairo_lang_test_utils::test_file_test_with_runner!(
+    wrapper_sizes,
+    "src/plugin/plugin_test_data",
+    {
+        wrapper_sizes: "wrapper_sizes",
+    },
+    WrapperSizesTestRunner
+);
+
 cairo_lang_test_utils::test_file_test_with_runner!(
     expand_contract,
     "src/plugin/plugin_test_data",
@@ -71,11 +186,212 @@ cairo_lang_test_utils::test_file_test_with_runner!(
         contract: "contract",
         events: "events",
         raw_output: "raw_output",
+        rest_args: "rest_args",
         storage: "storage",
         hello_starknet: "hello_starknet",
         dispatcher: "dispatcher",
         user_defined_types: "user_defined_types",
         l1_handler: "l1_handler",
+        default_constructor: "default_constructor",
+        felt252_panics: "felt252_panics",
+        generated_code: "generated_code",
+        wrapper_serde: "wrapper_serde",
+        max_entry_point_params: "max_entry_point_params",
+        missing_constructor_lint: "missing_constructor_lint",
     },
     ExpandContractTestRunner
 );
+
+#[test_case("felt252", "core::felt252")]
+#[test_case("u256", "core::integer::u256")]
+#[test_case("bool", "core::bool")]
+#[test_case("starknet::ContractAddress", "core::starknet::contract_address::ContractAddress")]
+#[test_case("LegacyMap::<felt252, u128>", "core::integer::u128")]
+#[test_case(
+    "LegacyMap::<starknet::ContractAddress, starknet::ClassHash>",
+    "core::starknet::class_hash::ClassHash"
+)]
+fn test_storage_accessors_types_match(member_type: &str, expected_value_type: &str) {
+    let db = &mut RootDatabase::builder()
+        .detect_corelib()
+        .with_semantic_plugin(Arc::new(StarkNetPlugin::default()))
+        .build()
+        .unwrap();
+    let crate_id = setup_test_crate(
+        db,
+        &formatdoc! {"
+            #[starknet::contract]
+            mod TestContract {{
+                #[storage]
+                struct Storage {{
+                    member: {member_type},
+                }}
+            }}
+        "},
+    );
+
+    let item_by_name = |module_id, name: &str| {
+        db.module_item_by_name(module_id, name.into()).unwrap().unwrap()
+    };
+    let contract_module = ModuleId::Submodule(extract_matches!(
+        item_by_name(ModuleId::CrateRoot(crate_id), "TestContract"),
+        ModuleItemId::Submodule
+    ));
+    let storage_module = ModuleId::Submodule(extract_matches!(
+        item_by_name(contract_module, STORAGE_MODULE),
+        ModuleItemId::Submodule
+    ));
+    let member_module = ModuleId::Submodule(extract_matches!(
+        item_by_name(storage_module, "member"),
+        ModuleItemId::Submodule
+    ));
+    let accessors_trait = extract_matches!(
+        item_by_name(member_module, "InternalContractStateTrait"),
+        ModuleItemId::Trait
+    );
+    let accessors = db.trait_functions(accessors_trait).unwrap();
+    let read_signature = db.trait_function_signature(accessors["read"]).unwrap();
+    let write_signature = db.trait_function_signature(accessors["write"]).unwrap();
+    let written_value = write_signature.params.iter().find(|param| param.name == "value").unwrap();
+
+    assert_eq!(read_signature.return_type, written_value.ty);
+    assert_eq!(read_signature.return_type.format(db), expected_value_type);
+}
+
+#[test_case("felt252", "core::felt252")]
+#[test_case("u256", "core::integer::u256")]
+#[test_case("bool", "core::bool")]
+#[test_case("(felt252, u8)", "(core::felt252, core::integer::u8)")]
+#[test_case("Option::<u128>", "core::option::Option::<core::integer::u128>")]
+#[test_case("Array::<felt252>", "core::array::Array::<core::felt252>")]
+#[test_case("Span::<u64>", "core::array::Span::<core::integer::u64>")]
+#[test_case(
+    "Option::<(starknet::ContractAddress, Array::<u256>)>",
+    "core::option::Option::<(core::starknet::contract_address::ContractAddress, \
+     core::array::Array::<core::integer::u256>)>"
+)]
+fn test_abi_output_type_matches_serialized_type(return_type: &str, expected_type: &str) {
+    let db = &mut RootDatabase::builder()
+        .detect_corelib()
+        .with_semantic_plugin(Arc::new(StarkNetPlugin::default()))
+        .build()
+        .unwrap();
+    let crate_id = setup_test_crate(
+        db,
+        &formatdoc! {"
+            #[starknet::contract]
+            mod TestContract {{
+                #[storage]
+                struct Storage {{}}
+
+                #[external(v0)]
+                fn foo(self: @ContractState, value: {return_type}) -> {return_type} {{
+                    value
+                }}
+            }}
+        "},
+    );
+
+    let contracts = find_contracts(db, &[crate_id]);
+    let abi = AbiBuilder::submodule_as_contract_abi(db, contracts[0].submodule_id).unwrap();
+    let abi_output_type = abi
+        .items
+        .iter()
+        .find_map(|item| try_extract_matches!(item, Item::Function))
+        .unwrap()
+        .outputs[0]
+        .ty
+        .clone();
+
+    // The type of the `Serde::serialize` call on the result in the wrapper.
+    let item_by_name = |module_id, name: &str| {
+        db.module_item_by_name(module_id, name.into()).unwrap().unwrap()
+    };
+    let external_module = ModuleId::Submodule(extract_matches!(
+        item_by_name(ModuleId::Submodule(contracts[0].submodule_id), "__external"),
+        ModuleItemId::Submodule
+    ));
+    let wrapper =
+        extract_matches!(item_by_name(external_module, "foo"), ModuleItemId::FreeFunction);
+    let wrapper_body = db.function_body(FunctionWithBodyId::Free(wrapper)).unwrap();
+    let serialized_type = wrapper_body
+        .exprs
+        .iter()
+        .find_map(|(_, expr)| {
+            let call = try_extract_matches!(expr, Expr::FunctionCall)?;
+            let impl_function = try_extract_matches!(
+                call.function.get_concrete(db).generic_function,
+                GenericFunctionId::Impl
+            )?;
+            if impl_function.function.name(db) != "serialize" {
+                return None;
+            }
+            let concrete_trait = impl_function.impl_id.concrete_trait(db).unwrap();
+            try_extract_matches!(concrete_trait.generic_args(db)[0], GenericArgumentId::Type)
+        })
+        .unwrap();
+
+    assert_eq!(abi_output_type, serialized_type.format(db));
+    assert_eq!(abi_output_type, expected_type);
+}
+
+#[test]
+fn test_find_overlapping_storage_members() {
+    let members = [
+        (BigUint::from(10_u32), Some(2)),
+        // Starts at the second slot of the first member.
+        (BigUint::from(11_u32), Some(1)),
+        // Starts right after the second member.
+        (BigUint::from(12_u32), Some(256)),
+        (BigUint::from(1000_u32), Some(1)),
+        // Of unknown size, so not checked for being overlapped by the next member.
+        (BigUint::from(2000_u32), None),
+        (BigUint::from(2001_u32), Some(1)),
+    ];
+    assert_eq!(find_overlapping_storage_members(&members), vec![(0, 1)]);
+}
+
+#[test_case(0, false; "at_the_limit")]
+#[test_case(1, true; "over_the_limit")]
+fn test_max_generated_contract_size(excess: usize, expect_diagnostic: bool) {
+    let contract_code = indoc! {"
+        #[starknet::contract]
+        mod TestContract {
+            #[storage]
+            struct Storage {}
+
+            #[external(v0)]
+            fn foo(ref self: ContractState, a: felt252) {}
+        }
+    "};
+    // The size of the generated code of the contract, without a limit.
+    let db = &mut plugin_test_db(StarkNetPlugin::default());
+    let test_module = setup_test_module(db, contract_code).unwrap();
+    let size = module_and_submodule_files(db, test_module.module_id)
+        .into_iter()
+        .filter(|file| file.file_name(db) == "contract")
+        .map(|file| db.file_content(file).unwrap().len())
+        .exactly_one()
+        .unwrap();
+
+    let max_size = size - excess;
+    let db = &mut plugin_test_db(
+        StarkNetPlugin::builder().with_max_generated_contract_size(max_size).build(),
+    );
+    setup_test_module(db, contract_code);
+
+    let expected_diagnostics = if expect_diagnostic {
+        formatdoc! {"
+            error: Plugin diagnostic: The generated code of the contract is {size} bytes long, \
+             exceeding the limit of {max_size} bytes. Consider splitting the contract.
+             --> lib.cairo:2:5
+            mod TestContract {{
+                ^**********^
+
+        "}
+    } else {
+        String::new()
+    };
+    assert_eq!(get_diagnostics_as_string(db), expected_diagnostics);
+}
+
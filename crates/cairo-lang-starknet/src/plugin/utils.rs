@@ -1,6 +1,13 @@
-use cairo_lang_syntax::node::ast::{self, Modifier};
+use cairo_felt::Felt252;
+use cairo_lang_defs::plugin::PluginDiagnostic;
+use cairo_lang_syntax::node::ast::{self, Modifier, OptionArgListParenthesized};
 use cairo_lang_syntax::node::db::SyntaxGroup;
-use cairo_lang_syntax::node::Terminal;
+use cairo_lang_syntax::node::helpers::QueryAttrs;
+use cairo_lang_syntax::node::ids::SyntaxStablePtrId;
+use cairo_lang_syntax::node::{Terminal, TypedSyntaxNode};
+use num_bigint::{BigInt, Sign};
+
+use super::consts::{RESERVED_IDENTIFIERS, SHORT_STRING_MAX_LENGTH};
 
 /// Checks if the parameter is defined as a ref parameter.
 pub fn is_ref_param(db: &dyn SyntaxGroup, param: &ast::Param) -> bool {
@@ -33,9 +40,47 @@ pub fn is_felt252(db: &dyn SyntaxGroup, type_ast: &ast::Expr) -> bool {
     arg_segment.ident(db).text(db) == "felt252"
 }
 
+/// Returns true if type_ast is `ContractAddress`, possibly with a path, e.g.
+/// `starknet::ContractAddress`.
+/// Does not resolve paths or type aliases.
+pub fn is_contract_address(db: &dyn SyntaxGroup, type_ast: &ast::Expr) -> bool {
+    let ast::Expr::Path(type_path) = type_ast else {
+        return false;
+    };
+    matches!(
+        type_path.elements(db).last(),
+        Some(ast::PathSegment::Simple(segment)) if segment.ident(db).text(db) == "ContractAddress"
+    )
+}
+
+/// Returns true if type_ast is `ExecutionInfo`, possibly with a path, e.g.
+/// `starknet::info::ExecutionInfo`.
+/// Does not resolve paths or type aliases.
+pub fn is_execution_info(db: &dyn SyntaxGroup, type_ast: &ast::Expr) -> bool {
+    let ast::Expr::Path(type_path) = type_ast else {
+        return false;
+    };
+    matches!(
+        type_path.elements(db).last(),
+        Some(ast::PathSegment::Simple(segment)) if segment.ident(db).text(db) == "ExecutionInfo"
+    )
+}
+
 /// Returns true if type_ast is `Span::<felt252>`.
 /// Does not resolve paths or type aliases.
 pub fn is_felt252_span(db: &dyn SyntaxGroup, type_ast: &ast::Expr) -> bool {
+    is_felt252_generic(db, type_ast, "Span")
+}
+
+/// Returns true if type_ast is `Array::<felt252>`.
+/// Does not resolve paths or type aliases.
+pub fn is_felt252_array(db: &dyn SyntaxGroup, type_ast: &ast::Expr) -> bool {
+    is_felt252_generic(db, type_ast, "Array")
+}
+
+/// Returns true if type_ast is `<generic_type_name>::<felt252>`.
+/// Does not resolve paths or type aliases.
+fn is_felt252_generic(db: &dyn SyntaxGroup, type_ast: &ast::Expr, generic_type_name: &str) -> bool {
     let ast::Expr::Path(type_path) = type_ast else {
         return false;
     };
@@ -46,7 +91,7 @@ pub fn is_felt252_span(db: &dyn SyntaxGroup, type_ast: &ast::Expr) -> bool {
         return false;
     };
 
-    if path_segment_with_generics.ident(db).text(db) != "Span" {
+    if path_segment_with_generics.ident(db).text(db) != generic_type_name {
         return false;
     }
     let args = path_segment_with_generics.generic_args(db).generic_args(db).elements(db);
@@ -57,6 +102,60 @@ pub fn is_felt252_span(db: &dyn SyntaxGroup, type_ast: &ast::Expr) -> bool {
     is_felt252(db, &arg_expr.value(db))
 }
 
+/// Returns the depth of the nested `Array`/`Span` types in type_ast, e.g. 2 for
+/// `Array::<Array::<felt252>>` and 0 for `felt252`.
+/// Does not resolve paths or type aliases.
+pub fn array_nesting_depth(db: &dyn SyntaxGroup, type_ast: &ast::Expr) -> usize {
+    match type_ast {
+        ast::Expr::Path(type_path) => {
+            let Some(ast::PathSegment::WithGenericArgs(segment)) =
+                type_path.elements(db).pop() else {
+                return 0;
+            };
+            let inner_depth = segment
+                .generic_args(db)
+                .generic_args(db)
+                .elements(db)
+                .iter()
+                .map(|arg| match arg {
+                    ast::GenericArg::Expr(arg_expr) => array_nesting_depth(db, &arg_expr.value(db)),
+                    ast::GenericArg::Underscore(_) => 0,
+                })
+                .max()
+                .unwrap_or_default();
+            let ident = segment.ident(db).text(db);
+            if ident == "Array" || ident == "Span" { inner_depth + 1 } else { inner_depth }
+        }
+        ast::Expr::Tuple(tuple) => tuple
+            .expressions(db)
+            .elements(db)
+            .iter()
+            .map(|expr| array_nesting_depth(db, expr))
+            .max()
+            .unwrap_or_default(),
+        _ => 0,
+    }
+}
+
+/// Returns the value of an unnamed argument that is a numeric or short string literal in the
+/// felt252 range.
+pub fn try_extract_felt252_literal(db: &dyn SyntaxGroup, arg: &ast::Arg) -> Option<BigInt> {
+    let ast::ArgClause::Unnamed(arg_clause) = arg.arg_clause(db) else {
+        return None;
+    };
+    let (value, suffix) = match arg_clause.value(db) {
+        ast::Expr::Literal(literal) => literal.numeric_value_and_suffix(db)?,
+        ast::Expr::ShortString(short_string) => {
+            (short_string.numeric_value(db)?, short_string.suffix(db))
+        }
+        _ => return None,
+    };
+    if !matches!(suffix.as_deref(), None | Some("felt252")) {
+        return None;
+    }
+    (value.sign() != Sign::Minus && value < Felt252::prime().into()).then_some(value)
+}
+
 /// Strips one preceding underscore from the given string slice, if any.
 pub fn maybe_strip_underscore(s: &str) -> &str {
     match s.strip_prefix('_') {
@@ -64,3 +163,67 @@ pub fn maybe_strip_underscore(s: &str) -> &str {
         None => s,
     }
 }
+
+/// Validates that the given marker attributes of the item are not given any arguments.
+pub fn forbid_attribute_args(
+    db: &dyn SyntaxGroup,
+    diagnostics: &mut Vec<PluginDiagnostic>,
+    item: &impl QueryAttrs,
+    attr_names: &[&str],
+) {
+    for attr_name in attr_names {
+        for attr in item.query_attr(db, attr_name) {
+            if let OptionArgListParenthesized::ArgListParenthesized(args) = attr.arguments(db) {
+                diagnostics.push(PluginDiagnostic {
+                    message: format!("The '{attr_name}' attribute does not accept arguments."),
+                    stable_ptr: args.args(db).stable_ptr().untyped(),
+                });
+            }
+        }
+    }
+}
+
+/// Validates that the name of an item the plugin generates code for is not one of the
+/// RESERVED_IDENTIFIERS. `item_kind` describes the item in the diagnostic, e.g. "a storage member".
+/// Returns false if the name is reserved.
+pub fn validate_name_not_reserved(
+    db: &dyn SyntaxGroup,
+    diagnostics: &mut Vec<PluginDiagnostic>,
+    name: &ast::TerminalIdentifier,
+    item_kind: &str,
+) -> bool {
+    let name_text = name.text(db);
+    if !RESERVED_IDENTIFIERS.contains(&name_text.as_str()) {
+        return true;
+    }
+    diagnostics.push(PluginDiagnostic {
+        message: format!(
+            "`{name_text}` is reserved and cannot be used as the name of {item_kind}."
+        ),
+        stable_ptr: name.stable_ptr().untyped(),
+    });
+    false
+}
+
+/// Validates that `text` fits in a short string literal, as required by the code the `attr_name`
+/// attribute generates from it. `item_kind` describes the text in the diagnostic, e.g. "Entry point
+/// names". Returns false if the text is too long.
+pub fn validate_short_string_length(
+    diagnostics: &mut Vec<PluginDiagnostic>,
+    text: &str,
+    item_kind: &str,
+    attr_name: &str,
+    stable_ptr: SyntaxStablePtrId,
+) -> bool {
+    if text.len() <= SHORT_STRING_MAX_LENGTH {
+        return true;
+    }
+    diagnostics.push(PluginDiagnostic {
+        message: format!(
+            "{item_kind} longer than {SHORT_STRING_MAX_LENGTH} characters are not supported by \
+             #[{attr_name}]."
+        ),
+        stable_ptr,
+    });
+    false
+}
@@ -0,0 +1,190 @@
+use cairo_lang_defs::ids::{
+    FunctionWithBodyId, LanguageElementId, ModuleFileId, ModuleId, TopLevelLanguageElementId,
+};
+use cairo_lang_defs::plugin::PluginDiagnostic;
+use cairo_lang_semantic::corelib::core_crate;
+use cairo_lang_semantic::db::SemanticGroup;
+use cairo_lang_semantic::items::functions::{GenericFunctionId, ImplGenericFunctionId};
+use cairo_lang_semantic::plugin::DynPluginAuxData;
+use cairo_lang_semantic::{Expr, FunctionId, TypeLongId};
+use cairo_lang_utils::unordered_hash_map::UnorderedHashMap;
+
+use super::aux_data::StarkNetContractAuxData;
+use super::consts::{EXTERNAL_ATTR, VIEW_EXTERNAL_ATTR};
+
+/// The full path of the trait reading and writing values of a type from and to storage.
+const STORAGE_ACCESS_TRAIT_PATH: &str = "core::starknet::storage_access::StorageAccess";
+/// The full path of the syscall writing a value to storage.
+const STORAGE_WRITE_SYSCALL_PATH: &str = "core::starknet::syscalls::storage_write_syscall";
+
+/// Returns the diagnostics of the views of the contract in the module, if any. Views are the
+/// external functions, free, in external impls or annotated in other impls, getting the contract
+/// state as a snapshot, and they may not write to storage. Functions annotated with
+/// VIEW_EXTERNAL_ATTR are mutating, and are not views.
+/// As the state can't be written through a snapshot, the writes are reported where the view
+/// bypasses it, e.g. by copying a storage member or by calling a function that writes to storage.
+pub fn view_diagnostics(
+    db: &dyn SemanticGroup,
+    module_id: ModuleId,
+) -> Vec<(ModuleFileId, PluginDiagnostic)> {
+    let mut diagnostics = vec![];
+    if !is_contract_module(db, module_id) {
+        return diagnostics;
+    }
+    let mut external_functions = vec![];
+    for free_function_id in db.module_free_functions_ids(module_id).unwrap_or_default() {
+        let function_id = FunctionWithBodyId::Free(free_function_id);
+        if has_attr(db, function_id, EXTERNAL_ATTR) {
+            external_functions.push(function_id);
+        }
+    }
+    for impl_def_id in db.module_impls_ids(module_id).unwrap_or_default() {
+        let Ok(attributes) = db.impl_def_attributes(impl_def_id) else {
+            continue;
+        };
+        let is_external_impl = attributes.iter().any(|attr| attr.id == EXTERNAL_ATTR);
+        let Ok(impl_functions) = db.impl_functions(impl_def_id) else {
+            continue;
+        };
+        for impl_function in impl_functions.values() {
+            let function_id = FunctionWithBodyId::Impl(*impl_function);
+            // The functions of other impls are external if annotated themselves.
+            if is_external_impl || has_attr(db, function_id, EXTERNAL_ATTR) {
+                external_functions.push(function_id);
+            }
+        }
+    }
+    let mut visited = UnorderedHashMap::default();
+    for function_id in external_functions {
+        // Functions annotated with VIEW_EXTERNAL_ATTR may write to storage, despite the snapshot.
+        if has_attr(db, function_id, VIEW_EXTERNAL_ATTR) {
+            continue;
+        }
+        let Ok(signature) = db.function_with_body_signature(function_id) else {
+            continue;
+        };
+        if !matches!(
+            signature.params.first().map(|param| db.lookup_intern_type(param.ty)),
+            Some(TypeLongId::Snapshot(_))
+        ) {
+            continue;
+        }
+        let Ok(body) = db.function_body(function_id) else {
+            continue;
+        };
+        for (_, expr) in body.exprs.iter() {
+            let Expr::FunctionCall(call) = expr else { continue; };
+            // The views themselves are not visited, so the results can't depend on them.
+            let mut lowest_dependency = usize::MAX;
+            if writes_to_storage(db, call.function, 0, &mut visited, &mut lowest_dependency) {
+                diagnostics.push((
+                    function_id.module_file_id(db.upcast()),
+                    PluginDiagnostic {
+                        message: "View functions cannot write to storage.".to_string(),
+                        stable_ptr: call.stable_ptr.untyped(),
+                    },
+                ));
+            }
+        }
+    }
+    diagnostics
+}
+
+/// Returns true if the function is annotated with the given attribute.
+fn has_attr(db: &dyn SemanticGroup, function_id: FunctionWithBodyId, attr_id: &str) -> bool {
+    let Ok(attributes) = db.function_with_body_attributes(function_id) else {
+        return false;
+    };
+    attributes.iter().any(|attr| attr.id == attr_id)
+}
+
+/// Returns true if the module is a contract, i.e. if it has a file generated for a contract.
+fn is_contract_module(db: &dyn SemanticGroup, module_id: ModuleId) -> bool {
+    let Ok(generated_file_infos) = db.module_generated_file_infos(module_id) else {
+        return false;
+    };
+    generated_file_infos.iter().flatten().any(|generated_file_info| {
+        generated_file_info
+            .aux_data
+            .0
+            .as_any()
+            .downcast_ref::<DynPluginAuxData>()
+            .and_then(|mapper| mapper.0.as_any().downcast_ref::<StarkNetContractAuxData>())
+            .is_some()
+    })
+}
+
+/// The state of a function visited by [writes_to_storage].
+enum VisitState {
+    /// The function is still being visited, at the given depth of the call chain.
+    InProgress(usize),
+    /// The function was visited, and whether it writes to storage.
+    Done(bool),
+}
+
+/// Returns true if calling the function may write to storage, directly or through the functions it
+/// calls. The functions of the corelib write to storage only through STORAGE_WRITE_SYSCALL_PATH
+/// and STORAGE_ACCESS_TRAIT_PATH, so their bodies are not visited.
+///
+/// `depth` is the depth of the call in the visited call chain. Recursive calls of functions that
+/// are still being visited are assumed not to write to storage, and `lowest_dependency` is set to
+/// the lowest depth of such a function. A result depending on a function visited at a lower depth
+/// than the visited function may still change once that function is visited, so it isn't kept in
+/// `visited`.
+fn writes_to_storage(
+    db: &dyn SemanticGroup,
+    function_id: FunctionId,
+    depth: usize,
+    visited: &mut UnorderedHashMap<FunctionWithBodyId, VisitState>,
+    lowest_dependency: &mut usize,
+) -> bool {
+    let concrete_function = function_id.get_concrete(db);
+    match concrete_function.generic_function {
+        GenericFunctionId::Extern(extern_function_id) => {
+            return extern_function_id.full_path(db.upcast()) == STORAGE_WRITE_SYSCALL_PATH;
+        }
+        GenericFunctionId::Impl(ImplGenericFunctionId { function, .. })
+            if function.trait_id(db.upcast()).full_path(db.upcast())
+                == STORAGE_ACCESS_TRAIT_PATH =>
+        {
+            return function.name(db.upcast()).starts_with("write");
+        }
+        _ => {}
+    }
+    // Functions of generic impls can't be resolved, and are not visited.
+    let Ok(Some(concrete_body_id)) = concrete_function.body(db) else {
+        return false;
+    };
+    let function_with_body_id = concrete_body_id.function_with_body_id(db);
+    if function_with_body_id.parent_module(db.upcast()).owning_crate(db.upcast()) == core_crate(db)
+    {
+        return false;
+    }
+    match visited.get(&function_with_body_id) {
+        Some(VisitState::Done(writes)) => return *writes,
+        Some(VisitState::InProgress(visited_depth)) => {
+            *lowest_dependency = (*lowest_dependency).min(*visited_depth);
+            return false;
+        }
+        None => {}
+    }
+    let Ok(body) = db.function_body(function_with_body_id) else {
+        return false;
+    };
+    visited.insert(function_with_body_id, VisitState::InProgress(depth));
+    let mut body_dependency = usize::MAX;
+    let writes = body.exprs.iter().any(|(_, expr)| {
+        matches!(
+            expr,
+            Expr::FunctionCall(call)
+                if writes_to_storage(db, call.function, depth + 1, visited, &mut body_dependency)
+        )
+    });
+    if writes || body_dependency >= depth {
+        visited.insert(function_with_body_id, VisitState::Done(writes));
+    } else {
+        visited.remove(&function_with_body_id);
+        *lowest_dependency = (*lowest_dependency).min(body_dependency);
+    }
+    writes
+}